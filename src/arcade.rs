@@ -0,0 +1,310 @@
+use crate::{
+    asset_loading, assets::GameAssets, audio::GameAudio, bull, cleanup, game_script, game_state,
+    leaderboard, menus, menus::name_entry::{NameEntry, NameInput}, save, shopkeeper,
+    title_screen::MenuAction, ui::text_size, AppState,
+};
+use bevy::prelude::*;
+use leafwing_input_manager::prelude::*;
+use serde::{Deserialize, Serialize};
+
+const SCORES_FILE: &str = "arcade.ron";
+const MAX_SCORES: usize = 10;
+const ROUND_POINTS: f32 = 1000.0;
+
+pub struct ArcadePlugin;
+impl Plugin for ArcadePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Arcade>()
+            .init_resource::<NameEntry>()
+            .insert_resource(save::read_file::<ArcadeScores>(SCORES_FILE).unwrap_or_default())
+            .add_system_set(SystemSet::on_enter(AppState::TitleScreen).with_system(end_run))
+            .add_system_set(SystemSet::on_update(AppState::InGame).with_system(apply_difficulty))
+            .add_system_set(SystemSet::on_enter(AppState::ArcadeOver).with_system(setup))
+            .add_system_set(
+                SystemSet::on_update(AppState::ArcadeOver)
+                    .with_system(handle_name_entry)
+                    .with_system(update_text.after(handle_name_entry))
+            )
+            .add_system_set(
+                SystemSet::on_exit(AppState::ArcadeOver).with_system(cleanup::<ArcadeOverCleanupMarker>),
+            );
+    }
+}
+
+// an endless run through the campaign's levels, each round a little harder
+#[derive(Default, Resource)]
+pub struct Arcade {
+    pub active: bool,
+    pub round: usize,
+    pub score: u32,
+}
+
+impl Arcade {
+    pub fn start(&mut self, campaign: &game_script::Campaign, game_script_state: &mut game_script::GameScriptState) -> bool {
+        let first_level = match campaign.levels().first() {
+            Some(level) => level.id.clone(),
+            None => return false,
+        };
+
+        println!("Starting arcade run");
+        self.active = true;
+        self.round = 0;
+        self.score = 0;
+        game_script_state.current = first_level;
+        true
+    }
+
+    // banks the round and moves on to the next level, wrapping around at the end
+    pub fn next_round(
+        &mut self,
+        live_score: f32,
+        campaign: &game_script::Campaign,
+        game_script_state: &mut game_script::GameScriptState,
+    ) {
+        self.score += self.round_score(live_score);
+        self.round += 1;
+
+        let levels = campaign.levels();
+        if !levels.is_empty() {
+            game_script_state.current = levels[self.round % levels.len()].id.clone();
+        }
+        println!("Arcade round {} at {:?}, score {}", self.round + 1, game_script_state.current, self.score);
+    }
+
+    // later rounds are worth more
+    pub fn round_score(&self, live_score: f32) -> u32 {
+        (live_score.max(0.0) * ROUND_POINTS * (self.round + 1) as f32) as u32
+    }
+
+    pub fn time_limit(&self, time_limit: f32) -> f32 {
+        time_limit * (1.0 - 0.08 * self.round as f32).max(0.5)
+    }
+
+    fn bull_speed(&self) -> f32 {
+        (1.0 + 0.1 * self.round as f32).min(2.0)
+    }
+
+    fn charge_limit(&self) -> f32 {
+        (1.0 - 0.1 * self.round as f32).max(0.4)
+    }
+
+    fn repair_time(&self) -> f32 {
+        (1.0 + 0.15 * self.round as f32).min(3.0)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ArcadeScore {
+    pub name: String,
+    pub score: u32,
+    pub rounds: usize,
+}
+
+#[derive(Serialize, Deserialize, Default, Resource)]
+#[serde(default)]
+pub struct ArcadeScores {
+    pub scores: Vec::<ArcadeScore>,
+}
+
+impl ArcadeScores {
+    fn qualifies(&self, score: u32) -> bool {
+        score > 0 && (self.scores.len() < MAX_SCORES || self.scores.iter().any(|s| score > s.score))
+    }
+
+    fn add(&mut self, entry: ArcadeScore) {
+        let position = self.scores.iter()
+                                  .position(|s| entry.score > s.score)
+                                  .unwrap_or(self.scores.len());
+        self.scores.insert(position, entry);
+        self.scores.truncate(MAX_SCORES);
+        save::write_file(SCORES_FILE, self);
+    }
+}
+
+fn end_run(
+    mut arcade: ResMut<Arcade>,
+) {
+    arcade.active = false;
+}
+
+// bulls and shopkeepers spawn with their defaults, scale them for the round
+fn apply_difficulty(
+    arcade: Res<Arcade>,
+    mut bulls: Query<&mut bull::Bull, Added<bull::Bull>>,
+    mut shopkeepers: Query<&mut shopkeeper::ShopKeeper, Added<shopkeeper::ShopKeeper>>,
+) {
+    if !arcade.active {
+        return;
+    }
+
+    for mut bull in &mut bulls {
+        bull.speed *= arcade.bull_speed();
+        bull.charge_limit *= arcade.charge_limit();
+    }
+    for mut keeper in &mut shopkeepers {
+        keeper.repair_time *= arcade.repair_time();
+    }
+}
+
+#[derive(Component)]
+struct ArcadeOverCleanupMarker;
+
+#[derive(Component)]
+struct NameText;
+
+#[derive(Component)]
+struct ScoresText;
+
+fn setup(
+    mut commands: Commands,
+    game_assets: Res<GameAssets>,
+    arcade: Res<Arcade>,
+    arcade_scores: Res<ArcadeScores>,
+    leaderboards: Res<leaderboard::Leaderboards>,
+    mut name_entry: ResMut<NameEntry>,
+    mut game_state: ResMut<game_state::GameState>,
+    text_scaler: text_size::TextScaler,
+) {
+    game_state.title_screen_cooldown = 1.0;
+    // the level leaderboards get the name too even if the run didn't make the arcade table
+    let entering = arcade_scores.qualifies(arcade.score) || leaderboards.has_unnamed();
+    *name_entry = NameEntry::new(&leaderboards.last_name, entering);
+
+    commands
+        .spawn(InputManagerBundle {
+            input_map: MenuAction::default_input_map(),
+            action_state: ActionState::default(),
+        })
+        .insert(ArcadeOverCleanupMarker);
+
+    let text_style = TextStyle {
+        font: game_assets.font.clone(),
+        font_size: text_scaler.scale(menus::BUTTON_LABEL_FONT_SIZE),
+        color: Color::WHITE,
+    };
+
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                position_type: PositionType::Absolute,
+                justify_content: JustifyContent::Center,
+                flex_direction: FlexDirection::ColumnReverse,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            background_color: Color::NONE.into(),
+            ..Default::default()
+        })
+        .insert(ArcadeOverCleanupMarker)
+        .with_children(|parent| {
+            parent.spawn(TextBundle {
+                text: Text::from_section(
+                    "Game Over",
+                    TextStyle {
+                        font: game_assets.font.clone(),
+                        font_size: text_scaler.scale(menus::DEFAULT_FONT_SIZE),
+                        color: Color::WHITE,
+                    }
+                ),
+                ..Default::default()
+            });
+            parent.spawn(TextBundle {
+                text: Text::from_section(
+                    format!("Score: {}  Rounds: {}", arcade.score, arcade.round),
+                    text_style.clone(),
+                ),
+                ..Default::default()
+            });
+            parent
+                .spawn(TextBundle {
+                    style: Style {
+                        margin: UiRect::all(Val::Percent(2.0)),
+                        ..Default::default()
+                    },
+                    text: Text::from_section("", text_style.clone()),
+                    ..Default::default()
+                })
+                .insert(NameText);
+            parent
+                .spawn(TextBundle {
+                    text: Text::from_section("", text_style.clone()),
+                    ..Default::default()
+                })
+                .insert(ScoresText);
+        });
+}
+
+fn handle_name_entry(
+    mut name_entry: ResMut<NameEntry>,
+    mut arcade_scores: ResMut<ArcadeScores>,
+    mut leaderboards: ResMut<leaderboard::Leaderboards>,
+    arcade: Res<Arcade>,
+    action_state: Query<&ActionState<MenuAction>>,
+    mut audio: GameAudio,
+    mut assets_handler: asset_loading::AssetsHandler,
+    mut game_assets: ResMut<GameAssets>,
+    mut game_state: ResMut<game_state::GameState>,
+    time: Res<Time>,
+) {
+    if game_state.title_screen_cooldown > 0.0 {
+        game_state.title_screen_cooldown -= time.delta_seconds();
+        return;
+    }
+
+    let action_state = match action_state.get_single() {
+        Ok(action_state) => action_state,
+        Err(_) => return,
+    };
+
+    if !name_entry.entering {
+        if action_state.just_pressed(MenuAction::Select) {
+            audio.play_sfx(&game_assets.blip);
+            assets_handler.load(AppState::TitleScreen, &mut game_assets, &game_state);
+        }
+        return;
+    }
+
+    match name_entry.handle_input(action_state) {
+        NameInput::Nothing => (),
+        NameInput::Changed => audio.play_sfx(&game_assets.blip),
+        NameInput::Confirmed => {
+            audio.play_sfx(&game_assets.blip);
+            let name = name_entry.name();
+            if arcade_scores.qualifies(arcade.score) {
+                arcade_scores.add(ArcadeScore {
+                    name: name.clone(),
+                    score: arcade.score,
+                    rounds: arcade.round,
+                });
+            }
+            leaderboards.name_unnamed(&name);
+            game_state.title_screen_cooldown = 0.3;
+        },
+    }
+}
+
+fn update_text(
+    name_entry: Res<NameEntry>,
+    arcade_scores: Res<ArcadeScores>,
+    mut name_texts: Query<&mut Text, (With<NameText>, Without<ScoresText>)>,
+    mut scores_texts: Query<&mut Text, (With<ScoresText>, Without<NameText>)>,
+) {
+    for mut text in &mut name_texts {
+        text.sections[0].value =
+            if name_entry.entering {
+                format!("New high score! {}", name_entry.display())
+            } else {
+                "Press select to continue".to_string()
+            };
+    }
+
+    for mut text in &mut scores_texts {
+        text.sections[0].value =
+            arcade_scores.scores.iter()
+                                .enumerate()
+                                .map(|(i, s)| format!("{:>2}. {} {:>7}  {} rounds", i + 1, s.name, s.score, s.rounds))
+                                .collect::<Vec::<_>>()
+                                .join("\n");
+    }
+}
@@ -1,4 +1,7 @@
-use crate::{assets::GameAssets, game_state, AppState, ingame, title_screen, splash};
+use crate::{
+    assets::GameAssets, game_state, AppState, ingame, title_screen, splash, cutscene::CutsceneScript,
+    game_script::Campaign,
+};
 use bevy::{asset::Asset, ecs::system::SystemParam, gltf::Gltf, prelude::*};
 use bevy_kira_audio::AudioSource;
 use std::marker::PhantomData;
@@ -70,6 +73,11 @@ impl<'w, 's> AssetsHandler<'w, 's> {
         self.state.set(AppState::Loading).unwrap();
     }
 
+    // for when everything the next state needs is already in GameAssets
+    pub fn skip_load(&mut self, next_state: AppState) {
+        self.state.set(next_state).unwrap();
+    }
+
     pub fn add_mesh(&mut self, mesh: &mut Handle<Mesh>, path: &str) {
         self.add_asset(mesh, path);
     }
@@ -90,6 +98,14 @@ impl<'w, 's> AssetsHandler<'w, 's> {
         self.add_asset(animation, path);
     }
 
+    pub fn add_cutscene(&mut self, cutscene: &mut Handle<CutsceneScript>, path: &str) {
+        self.add_asset(cutscene, path);
+    }
+
+    pub fn add_campaign(&mut self, campaign: &mut Handle<Campaign>, path: &str) {
+        self.add_asset(campaign, path);
+    }
+
     pub fn add_standard_mesh(&mut self, handle: &mut Handle<Mesh>, mesh: Mesh) {
         *handle = self.meshes.add(mesh);
     }
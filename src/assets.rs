@@ -1,4 +1,4 @@
-use crate::asset_loading;
+use crate::{asset_loading, cutscene::CutsceneScript, game_script::Campaign};
 use bevy::gltf::Gltf;
 use bevy::prelude::*;
 use bevy_kira_audio::AudioSource;
@@ -30,14 +30,10 @@ pub struct GameAssets {
     pub broken_plate: Handle<Gltf>,
     pub broken_mug: Handle<Gltf>,
     pub broken_fishbowl: Handle<Gltf>,
-    pub pregame: Handle<Gltf>,
-    pub level_one: Handle<Gltf>,
-    pub level_two: Handle<Gltf>,
-    pub level_three: Handle<Gltf>,
-    pub level_four: Handle<Gltf>,
-    pub level_five: Handle<Gltf>,
-    pub intro_level: Handle<Gltf>,
-    pub outro_level: Handle<Gltf>,
+    pub campaign: Handle<Campaign>,
+    pub level: Handle<Gltf>,
+    pub cutscene: Handle<CutsceneScript>,
+    pub chapter_bgm: Handle<AudioSource>,
 
     pub blip: Handle<AudioSource>,
     pub crash_sfx: Handle<AudioSource>,
@@ -8,6 +8,8 @@ impl Plugin for GameAudioPlugin {
         app.add_audio_channel::<MusicChannel>()
             .add_audio_channel::<SoundChannel>()
             .add_audio_channel::<TalkChannel>()
+            .init_resource::<CurrentBgm>()
+            .init_resource::<Volume>()
             .add_plugin(AudioPlugin);
     }
 }
@@ -19,40 +21,77 @@ pub struct SoundChannel;
 #[derive(Resource)]
 pub struct TalkChannel;
 
+// what's looping on the music channel so chapters that share a track
+// don't restart it
+#[derive(Default, Resource)]
+pub struct CurrentBgm(Option::<Handle<AudioSource>>);
+
+// multipliers from the options screen, applied on top of each channel's base volume
+#[derive(Resource)]
+struct Volume {
+    music: f32,
+    sound: f32,
+    talk: f32,
+}
+
+impl Default for Volume {
+    fn default() -> Self {
+        Volume {
+            music: 1.0,
+            sound: 1.0,
+            talk: 1.0,
+        }
+    }
+}
+
 #[derive(SystemParam)]
 pub struct GameAudio<'w, 's> {
     music_channel: Res<'w, AudioChannel<MusicChannel>>,
     sound_channel: Res<'w, AudioChannel<SoundChannel>>,
     talk_channel: Res<'w, AudioChannel<TalkChannel>>,
+    current_bgm: ResMut<'w, CurrentBgm>,
+    volume: ResMut<'w, Volume>,
 
     #[system_param(ignore)]
     phantom: PhantomData<&'s ()>,
 }
 
 impl<'w, 's> GameAudio<'w, 's> {
-    pub fn set_volume(&mut self) {
-        self.sound_channel.set_volume(0.2);
-        self.talk_channel.set_volume(0.2);
-        self.music_channel.set_volume(0.5);
+    pub fn set_volume(&mut self, music: f32, sound: f32, talk: f32) {
+        self.volume.music = music;
+        self.volume.sound = sound;
+        self.volume.talk = talk;
+
+        self.sound_channel.set_volume(0.2 * sound);
+        self.talk_channel.set_volume(0.2 * talk);
+        self.music_channel.set_volume(0.7 * music);
     }
     pub fn play_bgm(&mut self, handle: &Handle<AudioSource>) {
         self.music_channel.stop();
-        self.music_channel.set_volume(0.7);
+        self.music_channel.set_volume(0.7 * self.volume.music);
         self.music_channel.play(handle.clone()).looped();
+        self.current_bgm.0 = Some(handle.clone());
+    }
 
+    pub fn continue_bgm(&mut self, handle: &Handle<AudioSource>) {
+        if self.current_bgm.0.as_ref() != Some(handle) {
+            self.play_bgm(handle);
+        }
     }
 
     pub fn play_bgm_once(&mut self, handle: &Handle<AudioSource>) {
         self.music_channel.stop();
         self.music_channel.play(handle.clone());
+        self.current_bgm.0 = None;
     }
 
     pub fn stop_bgm(&mut self) {
         self.music_channel.stop();
+        self.current_bgm.0 = None;
     }
 
     pub fn play_sfx_repeat(&mut self, handle: &Handle<AudioSource>) {
-        self.sound_channel.set_volume(0.4);
+        self.sound_channel.set_volume(0.4 * self.volume.sound);
         self.sound_channel.play(handle.clone()).looped();
     }
 
@@ -61,7 +100,7 @@ impl<'w, 's> GameAudio<'w, 's> {
     }
 
     pub fn play_sfx(&mut self, handle: &Handle<AudioSource>) {
-        self.sound_channel.set_volume(0.2);
+        self.sound_channel.set_volume(0.2 * self.volume.sound);
         self.sound_channel.play(handle.clone());
     }
     pub fn play_talk(&mut self, handle: &Handle<AudioSource>) {
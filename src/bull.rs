@@ -1,9 +1,9 @@
 use bevy::prelude::*;
-use rand::thread_rng;
 use bevy::render::view::NoFrustumCulling;
 use std::collections::HashMap;
 use bevy_rapier3d::prelude::*;
 use bevy_camera_shake::Shake3d;
+use serde::Deserialize;
 
 use crate::{
     assets::GameAssets,
@@ -12,23 +12,45 @@ use crate::{
     player,
     ingame,
     dust,
+    bull_mind,
+    navmesh,
+    perception::Perception,
     audio::GameAudio,
 };
 
 const TRAUMA_AMOUNT: f32 = 0.5;
+// the collider before the archetype scales it
+pub const COLLIDER_HALF_EXTENT: f32 = 2.0;
+// a crash this hard gives a bull's full trauma, harder ones shake up to twice that
+const FULL_TRAUMA_FORCE: f32 = 5000.0;
+// impulse handed on per unit of the bull's momentum
+const SHOVE_IMPULSE: f32 = 0.0001;
+// how much of the shove tips things over instead of sliding them
+const SHOVE_TORQUE: f32 = 0.1;
+// a bull pushing through furniture keeps touching it, this stops it crashing every frame
+const CRASH_COOLDOWN: f32 = 0.5;
 const DUST_RATE: f32 = 0.3;
 const CHARGE_DUST_RATE: f32 = 0.5;
+const PANT_RATE: f32 = 0.6;
+// stamina goes from 0.0 to 1.0, these are per second
+const RUN_STAMINA_DRAIN: f32 = 0.2;
+const REST_STAMINA_REGEN: f32 = 0.1;
+const COLLISION_STAMINA_COST: f32 = 0.3;
+// a tired bull has to get back up to RESTED_STAMINA before it's not tired anymore
+const TIRED_STAMINA: f32 = 0.25;
+const RESTED_STAMINA: f32 = 0.6;
+const TIRED_SPEED: f32 = 0.5;
 pub struct BullPlugin;
 impl Plugin for BullPlugin {
     fn build(&self, app: &mut App) {
         app.add_system_set(
             SystemSet::on_update(AppState::InGame)
-                .with_system(update_bull_minds)
                 .with_system(animate_bull)
                 .with_system(handle_collisions)
                 .with_system(update_bulls)
                 .with_system(handle_bull_charge_event)
                 .with_system(handle_reset_bull_event_handler.before(update_bulls))
+                .with_system(apply_archetype)
         )
         .add_event::<BullChargeEvent>()
         .add_event::<ResetBullEvent>()
@@ -37,18 +59,30 @@ impl Plugin for BullPlugin {
 }
 
 const CHARGE_LIMIT: f32 = 2.5;
+// how much faster than walking a charge runs
+pub const RUN_SPEED: f32 = 2.5;
 
 pub struct BullChargeEvent {
-    pub charging: bool
+    pub charging: bool,
+    // whoever is waving the cape
+    pub player: Entity,
+    // the closest bull that's up for it when there isn't one picked
+    pub bull: Option::<Entity>,
 }
 
-#[derive(PartialEq)]
+#[derive(PartialEq, Clone, Copy, Debug)]
 pub enum BullState {
     Idle,
     Charging,
     Walking,
     Running,
     Collision,
+    // the rest are picked by bull_mind
+    Sniffing,
+    Stalking,
+    Pawing,
+    Bored,
+    Investigating,
 }
 
 impl Default for BullState {
@@ -57,9 +91,123 @@ impl Default for BullState {
     }
 }
 
+// which kind of bull a "bull" node spawns, a node named something like
+// "bullCalf" picks one and anything else uses the chapter's bull_kind
+#[derive(Deserialize, Clone, Copy, PartialEq, Debug)]
+pub enum BullKind {
+    Calf,
+    Standard,
+    Heavyweight,
+}
+
+impl Default for BullKind {
+    fn default() -> BullKind {
+        BullKind::Standard
+    }
+}
+
+impl BullKind {
+    pub fn from_name(name: &str) -> Option::<BullKind> {
+        if name.contains("Calf") {
+            Some(BullKind::Calf)
+        } else if name.contains("Heavy") {
+            Some(BullKind::Heavyweight)
+        } else if name.contains("Standard") {
+            Some(BullKind::Standard)
+        } else {
+            None
+        }
+    }
+
+    pub fn archetype(&self) -> BullArchetype {
+        match self {
+            BullKind::Calf => BullArchetype {
+                scale: 0.6,
+                speed: 35.0,
+                rotation_speed: 2.0,
+                friction: 0.01,
+                density: 1.0,
+                charge_limit: 1.5,
+                trauma: 0.2,
+                shove: 0.3,
+                animation_speed: 1.4,
+                smashes: false,
+                moves_sturdy: false,
+            },
+            BullKind::Standard => BullArchetype {
+                scale: 1.0,
+                speed: 25.0,
+                rotation_speed: 1.0,
+                friction: 0.01,
+                density: 5.0,
+                charge_limit: CHARGE_LIMIT,
+                trauma: TRAUMA_AMOUNT,
+                shove: 1.0,
+                animation_speed: 1.0,
+                smashes: false,
+                moves_sturdy: true,
+            },
+            BullKind::Heavyweight => BullArchetype {
+                scale: 1.5,
+                speed: 18.0,
+                rotation_speed: 0.6,
+                friction: 0.01,
+                density: 20.0,
+                charge_limit: 3.5,
+                trauma: 1.0,
+                shove: 2.5,
+                animation_speed: 0.7,
+                smashes: true,
+                moves_sturdy: true,
+            },
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct BullArchetype {
+    pub scale: f32,
+    pub speed: f32,
+    pub rotation_speed: f32,
+    pub friction: f32,
+    // for the collider, the mass comes from this and the scale
+    pub density: f32,
+    pub charge_limit: f32,
+    // how much the camera shakes when it crashes
+    pub trauma: f32,
+    // how hard it pushes furniture on top of what its weight and speed give
+    pub shove: f32,
+    pub animation_speed: f32,
+    // runs straight through furniture instead of bouncing off it
+    pub smashes: bool,
+    // whether it can shove furniture marked Sturdy, otherwise that's as good as a wall
+    pub moves_sturdy: bool,
+}
+
+impl BullArchetype {
+    pub fn mass(&self) -> f32 {
+        self.density * (2.0 * COLLIDER_HALF_EXTENT * self.scale).powi(3)
+    }
+
+    // how far from the furniture its middle has to stay to fit through a gap
+    pub fn clearance(&self) -> f32 {
+        COLLIDER_HALF_EXTENT * self.scale
+    }
+}
+
+// how easily a piece of furniture gets shoved, from "Shove<percent>" in the node name
+#[derive(Component)]
+pub struct Shove(pub f32);
+
+// heavy furniture only some bulls can move, from "Sturdy" in the node name
+#[derive(Component)]
+pub struct Sturdy;
+
 #[derive(Component)]
 pub struct Bull {
     pub current_animation: Handle<AnimationClip>,
+    pub kind: BullKind,
+    pub archetype: BullArchetype,
     pub state: BullState,
     pub random: f32,
     pub speed: f32,
@@ -67,40 +215,105 @@ pub struct Bull {
     pub friction: f32,
     pub mind_cooldown: f32,
     pub heading_to: Option::<Vec2>,
+    // somewhere to walk to, heading_to gets pointed at it every frame
+    pub target: Option::<Vec3>,
+    pub path: navmesh::Path,
+    // the player this bull is after
+    pub chasing: Option::<Entity>,
     pub dust_cooldown: f32,
     pub charging_cooldown: f32,
+    pub charge_limit: f32,
+    pub weights: bull_mind::BullWeights,
+    pub boredom: f32,
+    pub since_charge: f32,
+    // where the last noise came from and how long ago
+    pub noise: Option::<(Vec3, f32)>,
+    pub stamina: f32,
+    pub tired: bool,
+    pub pant_cooldown: f32,
+    pub crash_cooldown: f32,
 }
 
 impl Bull {
     pub fn can_think(&self) -> bool {
         self.mind_cooldown <= 0.0 && self.charging_cooldown <= 0.0
     }
+
+    pub fn can_charge(&self) -> bool {
+        self.charging_cooldown <= 0.0 && !self.tired
+    }
+
+    fn update_stamina(&mut self, delta: f32) {
+        match self.state {
+            BullState::Running => self.stamina -= RUN_STAMINA_DRAIN * delta,
+            BullState::Idle | BullState::Walking | BullState::Bored | BullState::Sniffing => {
+                self.stamina += REST_STAMINA_REGEN * delta
+            },
+            _ => (),
+        }
+        self.stamina = self.stamina.clamp(0.0, 1.0);
+
+        if self.stamina <= TIRED_STAMINA {
+            self.tired = true;
+        } else if self.stamina >= RESTED_STAMINA {
+            self.tired = false;
+        }
+    }
 }
 
 impl Default for Bull {
     fn default() -> Bull {
-        use rand::Rng;
-        let mut rng = rand::thread_rng();
+        Bull::new(BullKind::Standard)
+    }
+}
 
+impl Bull {
+    pub fn new(kind: BullKind) -> Bull {
+        let archetype = kind.archetype();
         Bull {
             current_animation: Handle::<AnimationClip>::default(),
+            kind,
+            archetype,
             state: BullState::Idle,
-            speed: 25.0,
-            rotation_speed: 1.0,
-            friction: 0.01,
-            random: rng.gen_range(0.5..1.0),
+            speed: archetype.speed,
+            rotation_speed: archetype.rotation_speed,
+            friction: archetype.friction,
+            // replay::seed_spawned picks the real one
+            random: 0.75,
             mind_cooldown: 0.0,
             charging_cooldown: 0.0,
+            charge_limit: archetype.charge_limit,
             dust_cooldown: 0.0,
             heading_to: None,
+            target: None,
+            path: navmesh::Path::new(archetype.clearance()),
+            chasing: None,
+            weights: bull_mind::BullWeights::default(),
+            boredom: 0.0,
+            since_charge: 0.0,
+            noise: None,
+            stamina: 1.0,
+            tired: false,
+            pant_cooldown: 0.0,
+            crash_cooldown: 0.0,
         }
     }
 }
 
+// the scene hook can't scale the bull since the "Tran" part of the name
+// can replace its transform afterwards, the collider scales along with it
+fn apply_archetype(
+    mut bulls: Query<(&Bull, &mut Transform), Added<Bull>>,
+) {
+    for (bull, mut transform) in &mut bulls {
+        transform.scale *= bull.archetype.scale;
+    }
+}
+
 fn handle_collisions(
     mut contact_force_events: EventReader<ContactForceEvent>,
     mut bulls: Query<(&mut Bull, &mut Velocity, &Transform), Without<ingame::BullCollide>>,
-    mut bull_colliders: Query<(&mut ingame::BullCollide, Option<&mut ExternalForce>, Option<&mut ExternalImpulse>), Without<Bull>>,
+    mut bull_colliders: Query<(&mut ingame::BullCollide, Option<&mut ExternalForce>, Option<&mut ExternalImpulse>, Option<&Shove>, Option<&Sturdy>), Without<Bull>>,
     mut shakeables: Query<&mut Shake3d>,
     mut dust_spawn_event_writer: EventWriter<dust::DustSpawnEvent>,
     game_assets: Res<GameAssets>,
@@ -109,52 +322,80 @@ fn handle_collisions(
     for e in contact_force_events.iter() {
         println!("contact force event {:?}", e.total_force_magnitude);
         let mut is_bull_collider = false;
-        for _ in bull_colliders.iter_many([e.collider1,e.collider2]) {
+        let mut is_furniture = false;
+        let mut is_sturdy = false;
+        for (_, _, impulse, _, sturdy) in bull_colliders.iter_many([e.collider1,e.collider2]) {
             is_bull_collider = true; 
+            // walls and the static level don't get pushed around
+            is_furniture |= impulse.is_some();
+            is_sturdy |= sturdy.is_some();
         }
 
-        let is_bull = bulls.get(e.collider1).is_ok() || bulls.get(e.collider2).is_ok();
-        println!("e: {} {} {}", e.total_force_magnitude, is_bull_collider, is_bull);
-        if is_bull_collider && is_bull {
+        // only the bull that actually hit something
+        let bull_entity = [e.collider1, e.collider2].into_iter().find(|entity| bulls.get(*entity).is_ok());
+        println!("e: {} {} {:?}", e.total_force_magnitude, is_bull_collider, bull_entity);
+        if let (true, Some(bull_entity)) = (is_bull_collider, bull_entity) {
 //            println!("hit wall");
             let mut bull_velocity = Vec3::default();
-            for (mut bull, mut velocity, transform) in &mut bulls {
+            let mut bull_shove = 0.0;
+            if let Ok((mut bull, mut velocity, transform)) = bulls.get_mut(bull_entity) {
+                let can_move = is_furniture && (!is_sturdy || bull.archetype.moves_sturdy);
                 if bull.state == BullState::Running {
-                    for mut shakeable in shakeables.iter_mut() {
-                        shakeable.trauma = f32::min(shakeable.trauma + TRAUMA_AMOUNT, 1.0);
+                    let crashed = bull.crash_cooldown <= 0.0;
+                    bull.crash_cooldown = CRASH_COOLDOWN;
+
+                    let trauma = bull.archetype.trauma * (e.total_force_magnitude / FULL_TRAUMA_FORCE).min(2.0);
+                    if crashed {
+                        for mut shakeable in shakeables.iter_mut() {
+                            shakeable.trauma = f32::min(shakeable.trauma + trauma, 1.0);
+                        }
                     }
-                    bull.state = BullState::Collision;
-                    bull.charging_cooldown = 1.0;
+
                     bull_velocity = velocity.linvel;
+                    if can_move {
+                        bull_shove = bull.archetype.shove * bull.archetype.mass();
+                    }
 
-                    velocity.linvel = -velocity.linvel;
+                    // a big enough bull keeps going and lets its weight do the rest
+                    if !(can_move && bull.archetype.smashes) {
+                        bull.state = BullState::Collision;
+                        bull.charging_cooldown = 1.0;
+                        bull.stamina = (bull.stamina - COLLISION_STAMINA_COST).max(0.0);
 
-                    audio.play_sfx(&game_assets.crash_sfx);
-                    dust_spawn_event_writer.send(dust::DustSpawnEvent {
-                        position: transform.translation + (transform.right() * 5.0),
-                        count: 10,
-                        spread: 6.0,
-                        rate: 0.5,
-                        dust_time_to_live: 3.0,
-                        emitter_time_to_live: 0.0,
-                        size: 2.0,
-                        image: game_assets.cloud_texture.image.clone(),
-                        ..default()
-                    });
+                        velocity.linvel = -velocity.linvel;
+                    }
+
+                    if crashed {
+                        audio.play_sfx(&game_assets.crash_sfx);
+                        dust_spawn_event_writer.send(dust::DustSpawnEvent {
+                            position: transform.translation + (transform.right() * 5.0),
+                            count: 10,
+                            spread: 6.0,
+                            rate: 0.5,
+                            dust_time_to_live: 3.0,
+                            emitter_time_to_live: 0.0,
+                            size: 2.0,
+                            image: game_assets.cloud_texture.image.clone(),
+                            ..default()
+                        });
+                    }
                 } else {
                     bull.heading_to = None;
                 }
             }
 
-//          for e in [e.collider1,e.collider2] {
-//              if let Ok((_, _force, impulse)) = bull_colliders.get_mut(e) {
-//                  if let Some(mut impulse) = impulse {
-//                      println!("Set velocity yo");
-//                      impulse.impulse = bull_velocity.normalize() * 1.5;
-//                      impulse.torque_impulse = (bull_velocity.normalize() * 1.5) / 10.0;
-//                  }
-//              }
-//          }
+            // hand the bull's momentum on to whatever it ran into, pushing
+            // from up high so tall things tip over
+            let direction = Vec3::new(bull_velocity.x, 0.0, bull_velocity.z).normalize_or_zero();
+            for e in [e.collider1,e.collider2] {
+                if let Ok((_, _force, impulse, shove, _)) = bull_colliders.get_mut(e) {
+                    if let Some(mut impulse) = impulse {
+                        let strength = SHOVE_IMPULSE * bull_shove * bull_velocity.length() * shove.map(|s| s.0).unwrap_or(1.0);
+                        impulse.impulse += direction * strength;
+                        impulse.torque_impulse += Vec3::Y.cross(direction) * strength * SHOVE_TORQUE;
+                    }
+                }
+            }
         }
     }
 }
@@ -168,15 +409,34 @@ pub struct BullMoveEvent {
 
 fn handle_bull_charge_event(
     mut charge_event_reader: EventReader<BullChargeEvent>,
-    mut bulls: Query<&mut Bull>,
+    mut bulls: Query<(Entity, &Transform, &mut Bull, &Perception)>,
+    players: Query<&Transform, (With<player::Player>, Without<Bull>)>,
 ) {
     for event in charge_event_reader.iter() {
-        for mut bull in &mut bulls {
-            if event.charging && bull.charging_cooldown <= 0.0 {
-                bull.charging_cooldown = CHARGE_LIMIT / 2.0;
+        if !event.charging {
+            continue;
+        }
+
+        let bull_entity = event.bull.or_else(|| {
+            let player_translation = players.get(event.player).ok()?.translation;
+            bulls.iter()
+                 .filter(|(_, _, bull, perception)| bull.can_charge() && perception.can_see(event.player))
+                 .min_by(|(_, a, _, _), (_, b, _, _)| {
+                     a.translation.distance(player_translation).total_cmp(&b.translation.distance(player_translation))
+                 })
+                 .map(|(entity, _, _, _)| entity)
+        });
+
+        // a bull has to see the cape to go for it, otherwise it's just something it heard
+        if let Some(Ok((_, _, mut bull, perception))) = bull_entity.map(|entity| bulls.get_mut(entity)) {
+            if bull.can_charge() && perception.can_see(event.player) {
+                bull.charging_cooldown = bull.charge_limit / 2.0;
                 bull.state = BullState::Charging;
-            } else if !event.charging {
-                // ?
+                bull.chasing = Some(event.player);
+                bull.target = None;
+                bull.heading_to = None;
+                bull.since_charge = 0.0;
+                bull.boredom = 0.0;
             }
         }
     }
@@ -197,23 +457,31 @@ fn animate_bull(
                     animation.resume();
                     bull.current_animation = game_assets.bull_collide.clone_weak();
                 }
-                animation.set_speed(2.0);
+                animation.set_speed(2.0 * bull.archetype.animation_speed);
             },
-            BullState::Walking => {
+            BullState::Walking | BullState::Sniffing | BullState::Investigating if bull.heading_to.is_some() => {
                 if bull.current_animation != game_assets.bull_walk {
                     animation.play(game_assets.bull_walk.clone_weak()).repeat();
                     animation.resume();
                     bull.current_animation = game_assets.bull_walk.clone_weak();
                 }
-                animation.set_speed(2.0);
+                animation.set_speed(2.0 * bull.archetype.animation_speed);
             },
-            BullState::Charging => {
+            BullState::Stalking => {
+                if bull.current_animation != game_assets.bull_walk {
+                    animation.play(game_assets.bull_walk.clone_weak()).repeat();
+                    animation.resume();
+                    bull.current_animation = game_assets.bull_walk.clone_weak();
+                }
+                animation.set_speed(1.5 * bull.archetype.animation_speed);
+            },
+            BullState::Charging | BullState::Pawing => {
                 if bull.current_animation != game_assets.bull_charge {
                     animation.play(game_assets.bull_charge.clone_weak()).repeat();
                     animation.resume();
                     bull.current_animation = game_assets.bull_charge.clone_weak();
                 }
-                animation.set_speed(2.0);
+                animation.set_speed(2.0 * bull.archetype.animation_speed);
             },
             BullState::Running => {
                 if bull.current_animation != game_assets.bull_run {
@@ -221,7 +489,7 @@ fn animate_bull(
                     animation.resume();
                     bull.current_animation = game_assets.bull_run.clone_weak();
                 }
-                animation.set_speed(3.0);
+                animation.set_speed(3.0 * bull.archetype.animation_speed);
             },
             _ => {
                 if bull.current_animation != game_assets.bull_idle {
@@ -229,7 +497,7 @@ fn animate_bull(
                     animation.resume();
                     bull.current_animation = game_assets.bull_idle.clone_weak();
                 }
-                animation.set_speed(2.0);
+                animation.set_speed(2.0 * bull.archetype.animation_speed);
             }
         }
     }
@@ -243,10 +511,10 @@ fn handle_reset_bull_event_handler(
     for event in reset_bull_event_writer.iter() {
         println!("AHHH");
 
-        for (mut transform, mut global_transform, mut velocity) in &mut bulls {
+        if let Ok((mut transform, mut global_transform, mut velocity)) = bulls.get_mut(event.0) {
             velocity.linvel = Vec3::default();
             velocity.angvel = Vec3::default();
-            *transform = Transform::from_xyz(0.0, 2.0, 0.0);
+            *transform = Transform::from_xyz(0.0, 2.0, 0.0).with_scale(transform.scale);
             *global_transform = GlobalTransform::from_xyz(0.0, 2.0, 0.0);
         }
 //      commands.entity(event.0)
@@ -262,9 +530,8 @@ fn handle_reset_bull_event_handler(
 fn update_bulls(
     mut commands: Commands,
     time: Res<Time>,
-    mut bulls: Query<(Entity, &mut Transform, &mut Bull, &mut Velocity), Without<player::Player>>,
+    mut bulls: Query<(Entity, &mut Transform, &mut Bull, &mut Velocity, &Perception), Without<player::Player>>,
     mut bull_move_event_reader: EventReader<BullMoveEvent>,
-    players: Query<(&Transform, &player::Player), Without<Bull>>,
     mut reset_bull_event_writer: EventWriter<ResetBullEvent>,
     mut dust_spawn_event_writer: EventWriter<dust::DustSpawnEvent>,
     game_assets: Res<GameAssets>,
@@ -275,7 +542,7 @@ fn update_bulls(
         move_events.entry(move_event.entity).or_insert(move_event);
     }
 
-    for (entity, mut transform, mut bull, mut velocity) in bulls.iter_mut() {
+    for (entity, mut transform, mut bull, mut velocity, perception) in bulls.iter_mut() {
         if transform.translation.is_nan() {
             println!("is nan!");
             println!("A{:?} {:?} {:?}", transform.translation, velocity.linvel, velocity.angvel);
@@ -287,13 +554,39 @@ fn update_bulls(
             continue;
         }
 
-        if bull.state == BullState::Idle {
+        bull.update_stamina(time.delta_seconds());
+        bull.crash_cooldown -= time.delta_seconds();
+        if bull.tired {
+            bull.pant_cooldown -= time.delta_seconds();
+            if bull.pant_cooldown <= 0.0 {
+                // little puffs out of the nose
+                dust_spawn_event_writer.send(dust::DustSpawnEvent {
+                    position: transform.translation + (transform.right() * 3.0) + Vec3::Y,
+                    count: 2,
+                    spread: 0.5,
+                    size: 0.5,
+                    dust_time_to_live: 1.0,
+                    image: game_assets.cloud_texture.image.clone(),
+                    ..default()
+                });
+                bull.pant_cooldown = PANT_RATE;
+            }
+
+            // out of breath mid run
+            if bull.state == BullState::Running && bull.stamina <= 0.0 {
+                bull.state = BullState::Idle;
+                bull.charging_cooldown = 0.0;
+            }
+        }
+
+        if bull.state == BullState::Idle || bull.state == BullState::Bored {
             continue;
         }
 
-        if bull.state == BullState::Charging {
-            for (player_transform, _) in &players {
-                let player_translation = player_transform.translation;
+        // goes for wherever it last saw them and just coasts once it forgets
+        let chasing = perception.target();
+        if bull.state == BullState::Charging || bull.state == BullState::Pawing {
+            if let Some(player_translation) = chasing {
                 let bull_translation = transform.translation;
                 let angle = (-(player_translation.z - bull_translation.z))
                        .atan2(player_translation.x - bull_translation.x);
@@ -315,17 +608,19 @@ fn update_bulls(
 
             if bull.charging_cooldown <= 0.0 {
                 bull.state = BullState::Running;
-                bull.charging_cooldown = CHARGE_LIMIT * 2.0;
+                bull.charging_cooldown = bull.charge_limit * 2.0;
             }
 
             continue;
         }
 
         let speed: f32 = match bull.state {
-                             BullState::Running => bull.speed * 2.5,
+                             BullState::Running => bull.speed * RUN_SPEED,
                              BullState::Collision => bull.speed * 1.5,
+                             BullState::Stalking => bull.speed * 0.6,
+                             BullState::Sniffing => bull.speed * 0.8,
                              _ => bull.speed,
-                         };
+                         } * if bull.tired { TIRED_SPEED } else { 1.0 };
         let rotation_speed: f32 = bull.rotation_speed;
         let friction: f32 = bull.friction;
 
@@ -337,8 +632,8 @@ fn update_bulls(
 //                  let direction = velocity.linvel.normalize();
 //                  velocity.linvel += (direction * speed) * time.delta_seconds();
 //              } else {
-                    for (player_transform, _) in &players {
-                        let acceleration = player_transform.translation - transform.translation;
+                    if let Some(player_translation) = chasing {
+                        let acceleration = player_translation - transform.translation;
                         let acceleration = Vec3::new(acceleration.x, 0.0, acceleration.z);
                         velocity.linvel += (acceleration.normalize() * speed) * time.delta_seconds();
                     }
@@ -397,48 +692,3 @@ fn update_bulls(
         }
     }
 }
-
-fn update_bull_minds(
-    time: Res<Time>,
-    mut bulls: Query<(Entity, &mut Transform, &mut Bull)>,
-    mut bull_move_event_writer: EventWriter<BullMoveEvent>,
-) {
-    for (entity, mut transform, mut bull) in bulls.iter_mut() {
-        // handling mind cool down
-        bull.mind_cooldown -= time.delta_seconds();
-        bull.mind_cooldown = bull.mind_cooldown.clamp(-10.0, 30.0);
-
-        if let Some(heading_to) = bull.heading_to {
-            bull_move_event_writer.send(BullMoveEvent {
-                entity,
-                direction: heading_to,
-            });
-        }
-
-        if !bull.can_think() {
-            continue;
-        }
-
-        bull.state = match bull.state {
-            BullState::Walking => {
-                BullState::Idle
-            },
-            _ => {
-                let random_direction = get_random_direction();
-                bull.heading_to = Some(random_direction);
-                BullState::Walking
-            },
-        };
-
-        bull.mind_cooldown = 2.0;
-    }
-}
-
-fn get_random_direction() -> Vec2 {
-    use rand::Rng;
-    let mut rng = rand::thread_rng();
-    let x: f32 = rng.gen_range(-100.0..100.0);
-    let z: f32 = rng.gen_range(-100.0..100.0);
-
-    Vec2::new(x, z).normalize()
-}
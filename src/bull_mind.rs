@@ -0,0 +1,250 @@
+use bevy::prelude::*;
+use rand::Rng;
+use serde::Deserialize;
+
+use crate::{
+    assets::GameAssets,
+    bull::{Bull, BullMoveEvent, BullState},
+    game_script,
+    navmesh,
+    perception::Perception,
+    player,
+    props,
+    replay,
+    AppState,
+};
+
+const ARRIVE_DISTANCE: f32 = 3.0;
+const SNIFF_RANGE: f32 = 30.0;
+const STALK_RANGE: f32 = 60.0;
+const CHARGE_RANGE: f32 = 35.0;
+// seconds before a noise is forgotten
+const NOISE_MEMORY: f32 = 8.0;
+// seconds without a charge before the bull is fully worked up
+const CHARGE_PATIENCE: f32 = 6.0;
+const BOREDOM_RATE: f32 = 0.04;
+const BORED_RECOVERY_RATE: f32 = 0.2;
+
+pub struct BullMindPlugin;
+impl Plugin for BullMindPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_set(
+            SystemSet::on_update(AppState::InGame)
+                .with_system(apply_chapter_weights.before(update_bull_minds))
+                .with_system(update_bull_minds)
+        );
+    }
+}
+
+// how much the bull likes doing each thing, 1.0 is normal and 0.0 means never.
+// chapters can set these with "bull: (charge: 2.0, ...)" in the campaign
+#[derive(Deserialize, Clone, Copy)]
+#[serde(default)]
+pub struct BullWeights {
+    pub idle: f32,
+    pub wander: f32,
+    pub sniff: f32,
+    pub stalk: f32,
+    pub charge: f32,
+    pub bored: f32,
+    pub investigate: f32,
+}
+
+impl Default for BullWeights {
+    fn default() -> Self {
+        BullWeights {
+            idle: 1.0,
+            wander: 1.0,
+            sniff: 1.0,
+            stalk: 1.0,
+            charge: 1.0,
+            bored: 1.0,
+            investigate: 1.0,
+        }
+    }
+}
+
+// what the bull knows about when it's deciding what to do next
+struct Surroundings {
+    position: Vec3,
+    player: Option::<Vec3>,
+    prop: Option::<Vec3>,
+}
+
+// 1.0 right on top of it down to 0.0 at the edge of the range
+fn closeness(from: Vec3, to: Option::<Vec3>, range: f32) -> f32 {
+    to.map(|to| (1.0 - from.distance(to) / range).clamp(0.0, 1.0))
+      .unwrap_or(0.0)
+}
+
+impl Bull {
+    // every state the mind can pick with how much the bull wants it right now
+    fn considerations(&self, surroundings: &Surroundings) -> [(BullState, f32); 7] {
+        let weights = &self.weights;
+        let position = surroundings.position;
+        let interest = 1.0 - self.boredom;
+        let can_charge = if self.can_charge() { 1.0 } else { 0.0 };
+        let knows_player = if surroundings.player.is_some() { 1.0 } else { 0.0 };
+        let worked_up = (self.since_charge / CHARGE_PATIENCE).min(1.0);
+        let noise = self.noise
+                        .map(|(_, age)| (1.0 - age / NOISE_MEMORY).max(0.0))
+                        .unwrap_or(0.0);
+
+        [
+            // a tired bull mostly wants to stand around and catch its breath
+            (BullState::Idle, weights.idle * if self.tired { 1.0 } else { 0.2 }),
+            (BullState::Walking, weights.wander * 0.3),
+            (BullState::Sniffing, weights.sniff * 0.8 * closeness(position, surroundings.prop, SNIFF_RANGE)),
+            (BullState::Stalking, weights.stalk * knows_player * interest * (0.3 + 0.7 * closeness(position, surroundings.player, STALK_RANGE))),
+            (BullState::Pawing, weights.charge * can_charge * interest * worked_up * closeness(position, surroundings.player, CHARGE_RANGE)),
+            (BullState::Bored, weights.bored * self.boredom),
+            (BullState::Investigating, weights.investigate * noise),
+        ]
+    }
+
+    fn enter_state(&mut self, state: BullState, surroundings: &Surroundings, rng: &mut impl Rng) {
+        self.target = None;
+        self.path.clear();
+        self.heading_to = None;
+        self.mind_cooldown = 2.0;
+
+        match state {
+            BullState::Walking => {
+                self.heading_to = Some(get_random_direction(rng));
+            },
+            BullState::Sniffing => {
+                self.target = surroundings.prop;
+                self.mind_cooldown = 4.0;
+            },
+            BullState::Stalking => {
+                self.target = surroundings.player;
+                self.mind_cooldown = 3.0;
+            },
+            BullState::Pawing => {
+                // update_bulls turns this into a run once it's done
+                self.charging_cooldown = self.charge_limit;
+                self.since_charge = 0.0;
+                self.boredom = 0.0;
+            },
+            BullState::Bored => {
+                self.mind_cooldown = 4.0;
+            },
+            BullState::Investigating => {
+                self.target = self.noise.map(|(position, _)| position);
+                self.mind_cooldown = 4.0;
+            },
+            _ => (),
+        }
+
+        self.state = state;
+    }
+}
+
+fn apply_chapter_weights(
+    game_assets: Res<GameAssets>,
+    game_script_state: Res<game_script::GameScriptState>,
+    campaigns: Res<Assets<game_script::Campaign>>,
+    mut bulls: Query<&mut Bull, Added<Bull>>,
+) {
+    let chapter = campaigns.get(&game_assets.campaign)
+                           .and_then(|campaign| game_script_state.chapter(campaign));
+    if let Some(chapter) = chapter {
+        for mut bull in &mut bulls {
+            bull.weights = chapter.bull;
+        }
+    }
+}
+
+pub fn update_bull_minds(
+    time: Res<Time>,
+    mut game_rng: ResMut<replay::GameRng>,
+    navmesh: Res<navmesh::NavMesh>,
+    mut bulls: Query<(Entity, &Transform, &mut Bull, &Perception)>,
+    players: Query<(Entity, &Transform), (With<player::Player>, Without<Bull>)>,
+    breakables: Query<(&GlobalTransform, &Visibility), With<props::Breakable>>,
+    mut bull_move_event_writer: EventWriter<BullMoveEvent>,
+) {
+    for (entity, transform, mut bull, perception) in bulls.iter_mut() {
+        let position = transform.translation;
+
+        // goes after the closest player it can see once it loses whoever it was after
+        let lost = bull.chasing.map(|player| !perception.can_see(player)).unwrap_or(true);
+        if lost && perception.target().is_none() {
+            bull.chasing = players.iter()
+                                  .filter(|(player, _)| perception.can_see(*player))
+                                  .min_by(|(_, a), (_, b)| a.translation.distance(position).total_cmp(&b.translation.distance(position)))
+                                  .map(|(player, _)| player)
+                                  .or(bull.chasing);
+        }
+        // only what it's seen, otherwise it has to go by what it hears
+        let player = perception.target();
+
+        // handling mind cool down
+        bull.mind_cooldown -= time.delta_seconds();
+        bull.mind_cooldown = bull.mind_cooldown.clamp(-10.0, 30.0);
+        bull.since_charge += time.delta_seconds();
+
+        bull.boredom = match bull.state {
+            BullState::Bored => bull.boredom - BORED_RECOVERY_RATE * time.delta_seconds(),
+            BullState::Charging | BullState::Pawing | BullState::Running => 0.0,
+            _ => bull.boredom + BOREDOM_RATE * time.delta_seconds(),
+        }.clamp(0.0, 1.0);
+
+        if let Some((_, age)) = bull.noise.as_mut() {
+            *age += time.delta_seconds();
+        }
+
+        if bull.state == BullState::Stalking {
+            bull.target = player;
+        }
+        if let Some(target) = bull.target {
+            let to_target = Vec2::new(target.x - position.x, target.z - position.z);
+            if to_target.length() < ARRIVE_DISTANCE || bull.path.reached_end(position, ARRIVE_DISTANCE) {
+                if bull.state == BullState::Investigating {
+                    bull.noise = None;
+                }
+                bull.target = None;
+                bull.heading_to = None;
+                bull.path.clear();
+            } else {
+                let waypoint = bull.path.steer(&navmesh, position, target, time.delta_seconds());
+                bull.heading_to = Vec2::new(waypoint.x - position.x, waypoint.z - position.z).try_normalize();
+            }
+        }
+
+        if let Some(heading_to) = bull.heading_to {
+            bull_move_event_writer.send(BullMoveEvent {
+                entity,
+                direction: heading_to,
+            });
+        }
+
+        if !bull.can_think() {
+            continue;
+        }
+
+        let surroundings = Surroundings {
+            position,
+            player,
+            prop: breakables.iter()
+                            .filter(|(_, visibility)| visibility.is_visible)
+                            .map(|(t, _)| t.translation())
+                            .min_by(|a, b| a.distance(position).total_cmp(&b.distance(position))),
+        };
+
+        // a little noise so the same bull doesn't always do the same thing
+        let (state, _) = bull.considerations(&surroundings)
+                             .into_iter()
+                             .map(|(state, score)| (state, score * game_rng.gen_range(0.8..1.2)))
+                             .fold((BullState::Idle, 0.0), |best, next| if next.1 > best.1 { next } else { best });
+
+        bull.enter_state(state, &surroundings, &mut *game_rng);
+    }
+}
+
+fn get_random_direction(rng: &mut impl Rng) -> Vec2 {
+    let x: f32 = rng.gen_range(-100.0..100.0);
+    let z: f32 = rng.gen_range(-100.0..100.0);
+
+    Vec2::new(x, z).normalize()
+}
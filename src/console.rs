@@ -0,0 +1,337 @@
+use crate::{
+    arcade, asset_loading, assets::GameAssets, cleanup, cutscene, dust, follow_text, game_camera,
+    game_script, game_state, groups, menus, player, replay, save, ui::text_size, AppState,
+};
+use bevy::prelude::*;
+use bevy::window::ReceivedCharacter;
+use bevy_camera_shake::Shake3d;
+use bevy_rapier3d::prelude::*;
+
+const HISTORY_LENGTH: usize = 12;
+const HELP: [&str; 12] = [
+    "goto <chapter>     jump to a chapter in the campaign",
+    "replay [file]      play back a recorded level, the last one by default",
+    "chapters           list the campaign's chapters",
+    "time <seconds>     set the time left in the level",
+    "score <0.0-1.0>    set the score",
+    "restore <group>    send a RestoreGroupEvent",
+    "dust [x y z]       spawn a dust cloud",
+    "text <message>     show follow text over the player",
+    "shake              add trauma to the camera",
+    "skip               skip to the end of the cutscene",
+    "physics            toggle the physics pipeline",
+    "camera             print the camera poses",
+];
+
+pub struct ConsolePlugin;
+impl Plugin for ConsolePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Console>()
+            .add_system(toggle_console)
+            .add_system_set(SystemSet::on_enter(AppState::Debug).with_system(setup))
+            .add_system_set(
+                SystemSet::on_update(AppState::Debug)
+                    .with_system(read_input)
+                    .with_system(run_command.after(read_input))
+                    .with_system(update_text.after(run_command))
+            )
+            .add_system_set(
+                SystemSet::on_exit(AppState::Debug).with_system(cleanup::<ConsoleCleanupMarker>),
+            )
+            .add_system_set(SystemSet::on_update(AppState::InGame).with_system(handle_goto))
+            .add_system_set(SystemSet::on_update(AppState::Cutscene).with_system(handle_goto));
+    }
+}
+
+#[derive(Component)]
+struct ConsoleCleanupMarker;
+
+#[derive(Component)]
+struct ConsoleText;
+
+#[derive(Default, Resource)]
+struct Console {
+    input: String,
+    history: Vec::<String>,
+    submitted: Option::<String>,
+    // loading a chapter has to wait until the console is closed
+    // since it sits on top of the level's state
+    goto: Option::<String>,
+}
+
+impl Console {
+    fn log(&mut self, line: impl Into<String>) {
+        let line = line.into();
+        println!("{}", line);
+        self.history.push(line);
+        if self.history.len() > HISTORY_LENGTH {
+            self.history.remove(0);
+        }
+    }
+}
+
+fn toggle_console(
+    keys: Res<Input<KeyCode>>,
+    mut state: ResMut<State<AppState>>,
+    replays: Res<replay::Replays>,
+) {
+    if !keys.just_pressed(KeyCode::Grave) {
+        return;
+    }
+
+    match state.current() {
+        // the replay's frame times only line up if it plays straight through
+        AppState::InGame if replays.is_playing() => println!("Console is closed during a replay"),
+        AppState::InGame | AppState::Cutscene => state.push(AppState::Debug).unwrap(),
+        AppState::Debug => state.pop().unwrap(),
+        _ => (),
+    }
+}
+
+fn handle_goto(
+    mut console: ResMut<Console>,
+    mut assets_handler: asset_loading::AssetsHandler,
+    mut game_assets: ResMut<GameAssets>,
+    game_state: ResMut<game_state::GameState>,
+    mut game_script_state: ResMut<game_script::GameScriptState>,
+) {
+    if let Some(chapter) = console.goto.take() {
+        game_script_state.current = chapter;
+        assets_handler.load(AppState::LoadWorld, &mut game_assets, &game_state);
+    }
+}
+
+fn setup(
+    mut commands: Commands,
+    game_assets: Res<GameAssets>,
+    console: Res<Console>,
+    text_scaler: text_size::TextScaler,
+) {
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(40.0)),
+                position_type: PositionType::Absolute,
+                position: UiRect {
+                    top: Val::Px(0.0),
+                    left: Val::Px(0.0),
+                    ..Default::default()
+                },
+                padding: UiRect::all(Val::Percent(1.0)),
+                align_items: AlignItems::FlexEnd,
+                ..Default::default()
+            },
+            background_color: Color::rgba(0.0, 0.0, 0.0, 0.8).into(),
+            ..Default::default()
+        })
+        .insert(ConsoleCleanupMarker)
+        .with_children(|parent| {
+            parent
+                .spawn(TextBundle {
+                    text: Text::from_section(
+                        console_text(&console),
+                        TextStyle {
+                            font: game_assets.font.clone(),
+                            font_size: text_scaler.scale(menus::BY_LINE_FONT_SIZE * 0.6),
+                            color: Color::WHITE,
+                        }
+                    ),
+                    ..Default::default()
+                })
+                .insert(ConsoleText);
+        });
+}
+
+fn read_input(
+    mut console: ResMut<Console>,
+    keys: Res<Input<KeyCode>>,
+    mut characters: EventReader<ReceivedCharacter>,
+) {
+    for character in characters.iter() {
+        let c = character.char;
+        // the toggle key comes through as a character too
+        if !c.is_control() && c != '`' && c != '~' {
+            console.input.push(c);
+        }
+    }
+
+    if keys.just_pressed(KeyCode::Back) {
+        console.input.pop();
+    }
+
+    if keys.just_pressed(KeyCode::Return) {
+        let input = std::mem::take(&mut console.input);
+        if !input.trim().is_empty() {
+            console.log(format!("> {}", input));
+            console.submitted = Some(input);
+        }
+    }
+}
+
+fn run_command(
+    mut console: ResMut<Console>,
+    mut state: ResMut<State<AppState>>,
+    mut game_state: ResMut<game_state::GameState>,
+    mut cutscene_state: ResMut<cutscene::CutsceneState>,
+    mut rapier: ResMut<RapierConfiguration>,
+    game_assets: Res<GameAssets>,
+    campaigns: Res<Assets<game_script::Campaign>>,
+    mut restore_group_event_writer: EventWriter<groups::RestoreGroupEvent>,
+    mut dust_spawn_event_writer: EventWriter<dust::DustSpawnEvent>,
+    mut follow_text_event_writer: EventWriter<follow_text::FollowTextEvent>,
+    cameras: Query<(&Transform, &game_camera::PanOrbitCamera), With<Camera3d>>,
+    mut shakeables: Query<&mut Shake3d>,
+    players: Query<Entity, With<player::Player>>,
+    // grouped to stay under the system param limit
+    (mut replays, mut arcade, mut save_slots): (ResMut<replay::Replays>, ResMut<arcade::Arcade>, ResMut<save::SaveSlots>),
+) {
+    if console.submitted.is_none() {
+        return;
+    }
+    let input = console.submitted.take().unwrap_or_default();
+    let mut args = input.split_whitespace();
+    let command = args.next().unwrap_or_default();
+    let args = args.collect::<Vec::<_>>();
+
+    match (command, args.as_slice()) {
+        ("help", _) => {
+            for line in HELP {
+                console.log(line);
+            }
+        },
+        ("chapters", _) => {
+            if let Some(campaign) = campaigns.get(&game_assets.campaign) {
+                let chapters = campaign.chapters.iter()
+                                                .map(|chapter| chapter.id.as_str())
+                                                .collect::<Vec::<_>>()
+                                                .join(" ");
+                console.log(chapters);
+            }
+        },
+        ("goto", [chapter]) => {
+            let exists = campaigns.get(&game_assets.campaign)
+                                  .map(|campaign| campaign.chapter(chapter).is_some())
+                                  .unwrap_or(false);
+            if exists {
+                console.goto = Some(chapter.to_string());
+                state.pop().unwrap();
+            } else {
+                console.log(format!("no chapter named {}", chapter));
+            }
+        },
+        ("replay", file) => {
+            let file = file.first().copied().unwrap_or(replay::LAST_REPLAY_FILE);
+            match save::read_file::<replay::Replay>(file) {
+                Some(recorded) => {
+                    console.log(format!("replaying {} ({} ticks)", recorded.chapter, recorded.ticks.len()));
+                    // plays like the level select so it doesn't touch anyone's save
+                    save_slots.active = None;
+                    arcade.active = recorded.arcade_round.is_some();
+                    arcade.round = recorded.arcade_round.unwrap_or(0);
+                    console.goto = Some(recorded.chapter.clone());
+                    replays.pending = Some(recorded);
+                    state.pop().unwrap();
+                },
+                None => console.log(format!("couldn't read {}", file)),
+            }
+        },
+        ("time", [seconds]) => match seconds.parse::<f32>() {
+            Ok(seconds) => {
+                game_state.current_time = seconds;
+                console.log(format!("time set to {}", seconds));
+            },
+            Err(_) => console.log("time needs a number"),
+        },
+        ("score", [score]) => match score.parse::<f32>() {
+            Ok(score) => {
+                let score = score.clamp(0.0, 1.0);
+                game_state.score = score;
+                game_state.live_score = score;
+                console.log(format!("score set to {}", score));
+            },
+            Err(_) => console.log("score needs a number"),
+        },
+        ("restore", [group_id]) => match group_id.parse::<usize>() {
+            Ok(group_id) => {
+                restore_group_event_writer.send(groups::RestoreGroupEvent {
+                    group_id
+                });
+                console.log(format!("restoring group {}", group_id));
+            },
+            Err(_) => console.log("restore needs a group id"),
+        },
+        ("dust", position) => {
+            let position = position.iter()
+                                   .filter_map(|n| n.parse::<f32>().ok())
+                                   .collect::<Vec::<_>>();
+            let position = match position.as_slice() {
+                [x, y, z] => Vec3::new(*x, *y, *z),
+                _ => Vec3::new(0.0, 0.5, 0.0),
+            };
+            dust_spawn_event_writer.send(dust::DustSpawnEvent {
+                position,
+                count: 10,
+                spread: 6.0,
+                rate: 0.5,
+                dust_time_to_live: 3.0,
+                emitter_time_to_live: 0.0,
+                size: 2.0,
+                ..default()
+            });
+            console.log(format!("dust at {:?}", position));
+        },
+        ("text", words) if !words.is_empty() => {
+            let follow = players.iter()
+                                .next()
+                                .map(follow_text::FollowThing::Entity)
+                                .unwrap_or(follow_text::FollowThing::Spot(Vec3::ZERO));
+            follow_text_event_writer.send(follow_text::FollowTextEvent {
+                follow,
+                text: words.join(" "),
+                color: Color::WHITE,
+                time_to_live: 6.0,
+            });
+        },
+        ("shake", _) => {
+            for mut shakeable in shakeables.iter_mut() {
+                shakeable.trauma = f32::min(shakeable.trauma + 0.5, 1.0);
+            }
+        },
+        ("skip", _) => {
+            cutscene_state.cutscene_index = 99999;
+            cutscene_state.waiting_on_input = false;
+            game_state.current_time = 0.0;
+        },
+        ("physics", _) => {
+            rapier.physics_pipeline_active = !rapier.physics_pipeline_active;
+            rapier.query_pipeline_active = !rapier.query_pipeline_active;
+            console.log(format!("physics {}", if rapier.physics_pipeline_active { "on" } else { "off" }));
+        },
+        ("camera", _) => {
+            for (transform, pan) in &cameras {
+                console.log(format!("C: {:?} {:?} {:?}", transform.translation, transform.rotation.to_axis_angle(), pan.focus));
+                console.log(format!("Forward: {:?}", transform.forward()));
+            }
+        },
+        _ => console.log(format!("unknown command: {} (try help)", input)),
+    }
+}
+
+fn update_text(
+    console: Res<Console>,
+    mut texts: Query<&mut Text, With<ConsoleText>>,
+) {
+    if !console.is_changed() {
+        return;
+    }
+
+    for mut text in &mut texts {
+        text.sections[0].value = console_text(&console);
+    }
+}
+
+fn console_text(console: &Console) -> String {
+    let mut lines = console.history.clone();
+    lines.push(format!("> {}_", console.input));
+    lines.join("\n")
+}
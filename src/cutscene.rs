@@ -5,6 +5,10 @@ use crate::{
 };
 use std::mem;
 use bevy::prelude::*;
+use bevy::asset::{AssetLoader, LoadContext, LoadedAsset};
+use bevy::reflect::TypeUuid;
+use bevy::utils::BoxedFuture;
+use serde::Deserialize;
 use rand::Rng;
 use leafwing_input_manager::prelude::ActionState;
 use leafwing_input_manager::InputManagerBundle;
@@ -18,7 +22,10 @@ impl Plugin for CutscenePlugin {
            .with_system(animate_textures)
            .with_system(handle_input)
            .with_system(move_camera)
+           .with_system(reload_cutscene.before(play_cutscene))
         )
+        .add_asset::<CutsceneScript>()
+        .init_asset_loader::<CutsceneScriptLoader>()
         .insert_resource(TextBox::default())
         .insert_resource(CutsceneTextureState::default())
         .add_system_set(SystemSet::on_enter(AppState::Cutscene)
@@ -153,7 +160,8 @@ impl TextBox {
     }
 }
 
-enum CutsceneTexture {
+#[derive(Deserialize, Clone)]
+pub enum CutsceneTexture {
     MatIdle,
     MatTalk,
     PaIdle,
@@ -168,14 +176,18 @@ struct CutsceneTextureState {
     pa: Vec::<CutsceneTexture>,
 }
 
+#[derive(Deserialize, Clone)]
 pub struct TextBoxText {
     text: String,
+    #[serde(default = "default_text_speed")]
     speed: f32,
+    #[serde(default)]
     auto: bool,
     speaking: DisplayCharacter,
 }
 
-enum DisplayCharacter {
+#[derive(Deserialize, Clone)]
+pub enum DisplayCharacter {
     Mat, Pa, None
 }
 
@@ -215,6 +227,7 @@ fn display_textbox(
     time: Res<Time>,
     mut audio: GameAudio,
     mut cutscene_state: ResMut<CutsceneState>,
+    settings: Res<menus::options::Settings>,
 ) {
     textbox.cooldown -= time.delta_seconds();     
     textbox.cooldown = textbox.cooldown.clamp(-3.0, 3.0);
@@ -270,7 +283,8 @@ fn display_textbox(
                         .insert(BackgroundColor(background_color));
                     });
 
-            if text_to_display.contains("b-") {
+            // lines marked auto get cut off and move on without waiting
+            if current_text.auto && current_text.text.is_empty() {
                 audio.play_talk(&game_assets.blip);
                 cutscene_state.waiting_on_input = false;
                 cutscene_state.cutscene_index += 1; 
@@ -282,7 +296,7 @@ fn display_textbox(
                 }
             }
 
-            current_speed = Some(current_text.speed);
+            current_speed = Some(current_text.speed * settings.text_speed.delay_multiplier());
             if current_text.text.is_empty() {
                 textbox.queued_text = None;
             }
@@ -391,6 +405,138 @@ fn handle_input(
     }
 }
 
+#[derive(Deserialize, TypeUuid)]
+#[uuid = "8ec2f6a6-376a-49a6-b417-16bae6042ce6"]
+pub struct CutsceneScript {
+    pub steps: Vec::<CutsceneStep>,
+    #[serde(default)]
+    pub finish: CutsceneFinish,
+}
+
+// every field is optional in the script files, anything left out just
+// keeps whatever the previous step set up
+#[derive(Deserialize, Default)]
+#[serde(default)]
+pub struct CutsceneStep {
+    camera: Option::<CameraPose>,
+    target_translation: CameraTarget<Vec3>,
+    target_rotation: CameraTarget<AxisAngle>,
+    #[serde(default = "default_camera_speed")]
+    camera_speed: f32,
+    stop_bgm: bool,
+    stop_sfx: bool,
+    bgm: Option::<String>,
+    sfx: Option::<String>,
+    sfx_repeat: Option::<String>,
+    mat: Option::<Vec::<CutsceneTexture>>,
+    pa: Option::<Vec::<CutsceneTexture>>,
+    player_animation: Option::<CharacterAnimation>,
+    bull_animation: Option::<CharacterAnimation>,
+    sirens: bool,
+    text: Option::<TextBoxText>,
+    advance: bool,
+}
+
+#[derive(Deserialize)]
+struct CameraPose {
+    translation: Vec3,
+    rotation: AxisAngle,
+}
+
+#[derive(Deserialize, Clone, Copy)]
+struct AxisAngle {
+    axis: Vec3,
+    angle: f32,
+}
+
+impl AxisAngle {
+    fn quat(&self) -> Quat {
+        Quat::from_axis_angle(self.axis, self.angle)
+    }
+}
+
+#[derive(Deserialize, Default)]
+enum CameraTarget<T> {
+    #[default]
+    Keep,
+    Clear,
+    MoveTo(T),
+}
+
+#[derive(Deserialize, Clone, Copy)]
+enum CharacterAnimation {
+    Idle,
+    Run,
+}
+
+// where the game goes afterwards comes from the campaign, this is just
+// for tidying up before it gets there
+#[derive(Deserialize, Default)]
+#[serde(default)]
+pub struct CutsceneFinish {
+    stop_bgm: bool,
+    stop_sfx: bool,
+}
+
+fn default_camera_speed() -> f32 {
+    2.0
+}
+
+fn default_text_speed() -> f32 {
+    0.10
+}
+
+#[derive(Default)]
+pub struct CutsceneScriptLoader;
+
+impl AssetLoader for CutsceneScriptLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), bevy::asset::Error>> {
+        Box::pin(async move {
+            let script = ron::Options::default()
+                .with_default_extension(ron::extensions::Extensions::IMPLICIT_SOME)
+                .from_bytes::<CutsceneScript>(bytes)?;
+            load_context.set_default_asset(LoadedAsset::new(script));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["cutscene.ron"]
+    }
+}
+
+// restart the current cutscene whenever its file changes on disk so
+// writers can see their edits without going back through the whole game
+fn reload_cutscene(
+    mut commands: Commands,
+    mut script_events: EventReader<AssetEvent<CutsceneScript>>,
+    mut cutscene_state: ResMut<CutsceneState>,
+    mut textbox: ResMut<TextBox>,
+    game_assets: Res<GameAssets>,
+    game_script_state: Res<game_script::GameScriptState>,
+    text_container: Query<&Children, With<CutsceneTextContainerMarker>>,
+) {
+    for event in script_events.iter() {
+        if let AssetEvent::Modified { handle } = event {
+            if *handle != game_assets.cutscene { continue; }
+
+            println!("Cutscene: reloading {:?}", game_script_state.current);
+            cutscene_state.cutscene_index = 0;
+            cutscene_state.waiting_on_input = false;
+            textbox.queued_text = None;
+            for children in text_container.iter() {
+                for entity in children.iter() {
+                    commands.get_or_spawn(*entity).despawn_recursive();
+                }
+            }
+        }
+    }
+}
+
 fn play_cutscene(
     mut commands: Commands,
     mut cutscene_state: ResMut<CutsceneState>,
@@ -402,10 +548,12 @@ fn play_cutscene(
     mut game_script_state: ResMut<game_script::GameScriptState>,
     mut cutscene_texture_state: ResMut<CutsceneTextureState>,
     mut animations: Query<&mut AnimationPlayer>,
+    cutscene_scripts: Res<Assets<CutsceneScript>>,
+    campaigns: Res<Assets<game_script::Campaign>>,
+    asset_server: Res<AssetServer>,
     pointlights: Query<Entity, With<PointLight>>,
     players: Query<Entity, With<player::Player>>,
     bulls: Query<Entity, With<bull::Bull>>,
-//    mut ingame_ui_textbox: ResMut<ingame_ui::TextBox>,
     mut audio: GameAudio,
     text_container: Query<&Children, With<CutsceneTextContainerMarker>>,
 ) {
@@ -413,1378 +561,127 @@ fn play_cutscene(
 //    println!("{:?} {:?}", camera.translation, camera.rotation.to_axis_angle());
     if cutscene_state.waiting_on_input { return; }
 
-    cutscene_state.camera_speed = 2.0;
-    cutscene_state.waiting_on_input = true;
-    let text_speed = 0.10;
-
-    println!("Cutscene: matching {:?}", game_script_state.current);
-//    *ingame_ui_textbox = ingame_ui::TextBox::default(); // clear out any banter or commentary
-    match game_script_state.current {
-        game_script::GameScript::IntroCutscene => {
-            match cutscene_state.cutscene_index {
-                0 => {
-                    println!("Cutscene: 0 in intro");
-                    camera.translation = Vec3::new(19.605387, -10.73157346, 20.539804);
-                    camera.rotation = Quat::from_axis_angle(Vec3::new(-0.030079605, -0.99812686, -0.05320679), 2.071217);
-                    cutscene_state.camera_speed = 1.0; 
-                    audio.stop_bgm();
-                    audio.play_bgm(&game_assets.intro_bgm);
-                    audio.play_sfx_repeat(&game_assets.fire_sfx);
-                    cutscene_state.target_camera_translation = Some(Vec3::new(18.70001, 1.6389314, 20.061293));
-                    cutscene_state.target_camera_rotation = Some(Quat::from_axis_angle(Vec3::new(-0.07476175, -0.98985404, -0.12082917), 2.0425286));
-//                  cutscene_texture_state.pa = vec!(CutsceneTexture::PaIdle, 
-//                                                   CutsceneTexture::PaTalk);
-                    cutscene_texture_state.mat = vec!(CutsceneTexture::MatIdle, 
-                                                     CutsceneTexture::MatTalk);
-                    textbox.queued_text = Some(TextBoxText {
-                        text: "MAT: We didn't have a single customer come in this week.".to_string(),
-                        speed: text_speed,
-                        auto: false,
-                        speaking: DisplayCharacter::Mat,
-                    });
-                },
-                1 => {
-                    cutscene_texture_state.mat = vec!(CutsceneTexture::MatIdle, 
-                                                     CutsceneTexture::MatTalk);
-                    textbox.queued_text = Some(TextBoxText {
-                        text: "MAT: I don't know how we're going to make rent this month.".to_string(),
-                        speed: text_speed,
-                        auto: false,
-                        speaking: DisplayCharacter::Mat,
-                    });
-                },
-                2 => {
-                    cutscene_texture_state.mat = vec!(CutsceneTexture::MatIdle);
-                    cutscene_texture_state.pa = vec!(CutsceneTexture::PaIdle, 
-                                                     CutsceneTexture::PaTalk);
-                    textbox.queued_text = Some(TextBoxText {
-                        text: "PA: There's too much competition! We need to think outside of the soap box.".to_string(),
-                        speed: text_speed,
-                        auto: false,
-                        speaking: DisplayCharacter::Pa,
-                    });
-                },
-                3 => {
-                    cutscene_texture_state.mat = vec!(CutsceneTexture::MatIdle);
-                    cutscene_texture_state.pa = vec!(CutsceneTexture::PaIdle, 
-                                                     CutsceneTexture::PaTalk);
-
-                    textbox.queued_text = Some(TextBoxText {
-                        text: "PA: What are our strengths? Our resources?".to_string(),
-                        speed: text_speed,
-                        auto: false,
-                        speaking: DisplayCharacter::Pa,
-                    });
-                },
-                4 => {
-                    cutscene_texture_state.mat = vec!(CutsceneTexture::MatIdle, 
-                                                     CutsceneTexture::MatTalk);
-                    cutscene_texture_state.pa = vec!(CutsceneTexture::PaIdle); 
-                    textbox.queued_text = Some(TextBoxText {
-                        text: "MAT: All I have is this cape from my halloween costume.".to_string(),
-                        speed: text_speed,
-                        auto: false,
-                        speaking: DisplayCharacter::Mat,
-                    });
-                },
-                5 => {
-                    cutscene_texture_state.mat = vec!(CutsceneTexture::MatIdle, 
-                                                     CutsceneTexture::MatTalk);
-                    cutscene_texture_state.pa = vec!(CutsceneTexture::PaIdle); 
-                    cutscene_state.target_camera_translation = Some(Vec3::new(16.895172, 1.6389309, 21.279295));
-                    cutscene_state.target_camera_rotation = Some(Quat::from_axis_angle(Vec3::new(-0.10611979, -0.9900699, -0.09219614), 1.440496));
-                    textbox.queued_text = Some(TextBoxText {
-                        text: "MAT: and my fully-trained 2,000 lb bull.".to_string(),
-                        speed: text_speed,
-                        auto: false,
-                        speaking: DisplayCharacter::Mat,
-                    });
-                },
-                6 => {
-                    cutscene_texture_state.mat = vec!(CutsceneTexture::MatIdle);
-                    cutscene_texture_state.pa = vec!(CutsceneTexture::PaIdle, 
-                                                     CutsceneTexture::PaTalk);
-
-                    textbox.queued_text = Some(TextBoxText {
-                        text: "PA: Ah yes.. your pet bull. I keep forgetting.".to_string(),
-                        speed: text_speed,
-                        auto: false,
-                        speaking: DisplayCharacter::Pa,
-                    });
-                },
-                7 => {
-                    cutscene_texture_state.mat = vec!(CutsceneTexture::MatIdle);
-                    cutscene_texture_state.pa = vec!(CutsceneTexture::PaIdle, 
-                                                     CutsceneTexture::PaTalk);
-
-                    cutscene_state.target_camera_translation = Some(Vec3::new(18.70001, 1.6389314, 20.061293));
-                    cutscene_state.target_camera_rotation = Some(Quat::from_axis_angle(Vec3::new(-0.07476175, -0.98985404, -0.12082917), 2.0425286));
-
-                    textbox.queued_text = Some(TextBoxText {
-                        text: "PA: hmm... hold on.. that gives me an idea.".to_string(),
-                        speed: text_speed,
-                        auto: false,
-                        speaking: DisplayCharacter::Pa,
-                    });
-                },
-                8 => {
-                    cutscene_texture_state.mat = vec!(CutsceneTexture::MatIdle);
-                    cutscene_texture_state.pa = vec!(CutsceneTexture::PaIdle, 
-                                                     CutsceneTexture::PaTalk);
-                    camera.translation = Vec3::new(20.503273, 1.9418178, 19.837416);
-                    camera.rotation = Quat::from_axis_angle(Vec3::new(0.033396073, 0.99048394, 0.1335149), 3.6273122);
-                    cutscene_state.target_camera_translation = Some(Vec3::new(21.84123, 1.9418178, 22.34461));
-                    cutscene_state.target_camera_rotation = Some(Quat::from_axis_angle(Vec3::new(0.033396073, 0.99048394, 0.1335149), 3.6273122));
-                    cutscene_state.camera_speed = 0.25; 
-
-                    textbox.queued_text = Some(TextBoxText {
-                        text: "PA: Yes... YES...".to_string(),
-                        speed: text_speed,
-                        auto: false,
-                        speaking: DisplayCharacter::Pa,
-                    });
-                },
-                9 => {
-                    cutscene_texture_state.mat = vec!(CutsceneTexture::MatIdle);
-                    cutscene_texture_state.pa = vec!(CutsceneTexture::PaIdle, 
-                                                     CutsceneTexture::PaTalk);
-                    cutscene_state.camera_speed = 0.25; 
-
-                    textbox.queued_text = Some(TextBoxText {
-                        text: "PA: I think we're going to be alright.".to_string(),
-                        speed: text_speed,
-                        auto: false,
-                        speaking: DisplayCharacter::Pa,
-                    });
-                },
-                10 => {
-                    cutscene_texture_state.mat = vec!(CutsceneTexture::MatIdle);
-                    cutscene_texture_state.pa = vec!(CutsceneTexture::PaIdle); 
-                                                     
-                    cutscene_state.camera_speed = 0.25; 
-
-                    textbox.queued_text = Some(TextBoxText {
-                        text: "MAT: ...".to_string(),
-                        speed: text_speed,
-                        auto: false,
-                        speaking: DisplayCharacter::None,
-                    });
-                },
-                11 => {
-                    cutscene_texture_state.mat = vec!(CutsceneTexture::MatIdle);
-                    cutscene_texture_state.pa = vec!(CutsceneTexture::PaLook); 
-
-                    audio.stop_bgm();
-                    textbox.queued_text = Some(TextBoxText {
-                        text: "MAT: we should probably put out this fire now.".to_string(),
-                        speed: text_speed,
-                        auto: false,
-                        speaking: DisplayCharacter::Mat,
-                    });
-                },
-                12 => {
-                    cutscene_texture_state.mat = vec!(CutsceneTexture::MatIdle);
-                    cutscene_texture_state.pa = vec!(CutsceneTexture::PaIdle, 
-                                                     CutsceneTexture::PaTalk);
-
-                    camera.translation = Vec3::new(16.895172, 1.6389309, 21.279295);
-                    camera.rotation = Quat::from_axis_angle(Vec3::new(-0.10611979, -0.9900699, -0.09219614), 1.440496);
-                    cutscene_state.target_camera_translation = None;
-                    cutscene_state.target_camera_rotation = None;
-
-                    textbox.queued_text = Some(TextBoxText {
-                        text: "PA: Yeah, we need to stop making these in here..".to_string(),
-                        speed: text_speed,
-                        auto: false,
-                        speaking: DisplayCharacter::Pa,
-                    });
-                },
-                _ => {
-                    println!("Cutscene: Done in intro");
-                    camera.translation = Vec3::new(game_camera::INGAME_CAMERA_X, 
-                                                   game_camera::INGAME_CAMERA_Y, 
-                                                   0.0);
-                    camera.rotation = Quat::from_axis_angle(game_camera::INGAME_CAMERA_ROTATION_AXIS, 
-                                                game_camera::INGAME_CAMERA_ROTATION_ANGLE);
-                    cutscene_state.target_camera_translation = None;
-                    cutscene_state.target_camera_rotation = None;
-                    cutscene_state.cutscene_index = 0;
-                    audio.stop_bgm();
-                    audio.stop_sfx();
-                    game_script_state.next();
-                    cutscene_state.waiting_on_input = false;
-                    assets_handler.load(AppState::LoadWorld, &mut game_assets, &game_state);
-                }
-            }
-        },
-        game_script::GameScript::PreLevelOneCutscene => {
-            match cutscene_state.cutscene_index {
-                0 => {
-                    cutscene_state.target_camera_translation = Some(Vec3::new(6.8157444, 30.6143255, -2.526128));
-                    cutscene_state.target_camera_rotation = None;
-                    camera.translation = Vec3::new(0.0, 0.0, 0.0);
-                    camera.rotation = Quat::from_axis_angle(Vec3::new(-0.051877804, 0.9947759, 0.08791898), 2.080009);
-
-                    cutscene_state.input_cooldown = 0.5;
-                    cutscene_state.waiting_on_input = false;
-                    cutscene_state.cutscene_index += 1;
-                    // clear out existing text
-                    for children in text_container.iter() {
-                        for entity in children.iter() {
-                            commands.get_or_spawn(*entity).despawn_recursive();
-                        }
-                    }
-                },
-                1 => {
-                    cutscene_state.target_camera_translation = None;
-                    cutscene_state.target_camera_rotation = None;
-                    camera.translation = Vec3::new(6.8157444, 30.6143255, -2.526128);
-                    camera.rotation = Quat::from_axis_angle(Vec3::new(-0.051877804, 0.9947759, 0.08791898), 2.080009);
-
-                    for entity in &players {
-                        println!("setting player animation");
-                        let mut animation = animations.get_mut(entity).unwrap();
-                        animation.play(game_assets.matador_run.clone_weak()).repeat();
-                        animation.set_speed(4.0);
-                    }
-
-                    for entity in &bulls {
-                        println!("setting bull animation");
-                        let mut animation = animations.get_mut(entity).unwrap();
-                        animation.play(game_assets.bull_run.clone_weak()).repeat();
-                        animation.set_speed(3.0);
-                        animation.resume();
-                    }
-
-                    cutscene_state.target_camera_translation = Some(Vec3::new(6.8157444, 2.0143255, -2.526128));
-
-                    audio.stop_bgm();
-                    audio.play_bgm(&game_assets.pregame_bgm);
+    // still loading, or reloading after the file was edited
+    let script = match cutscene_scripts.get(&game_assets.cutscene) {
+        Some(script) => script,
+        None => return,
+    };
 
-                    textbox.queued_text = Some(TextBoxText {
-                        text: "PA: OK! Here's the plan.".to_string(),
-                        speed: text_speed,
-                        auto: false,
-                        speaking: DisplayCharacter::Pa,
-                    });
-                },
-                2 => {
-                    for entity in &players {
-                        println!("setting player animation");
-                        let mut animation = animations.get_mut(entity).unwrap();
-                        animation.play(game_assets.matador_run.clone_weak()).repeat();
-                        animation.set_speed(4.0);
-                    }
-
-                    for entity in &bulls {
-                        println!("setting bull animation");
-                        let mut animation = animations.get_mut(entity).unwrap();
-                        animation.play(game_assets.bull_run.clone_weak()).repeat();
-                        animation.set_speed(3.0);
-                        animation.resume();
-                    }
-
-                    cutscene_state.target_camera_translation = Some(Vec3::new(6.8157444, 2.0143255, -2.526128));
-
-                    textbox.queued_text = Some(TextBoxText {
-                        text: "PA: You and your bull go to each antique shop in town.".to_string(),
-                        speed: text_speed,
-                        auto: false,
-                        speaking: DisplayCharacter::Pa,
-                    });
-                },
-                3 => {
-                    textbox.queued_text = Some(TextBoxText {
-                        text: "PA: And then... you wreck the place.".to_string(),
-                        speed: text_speed,
-                        auto: false,
-                        speaking: DisplayCharacter::Pa,
-                    });
-                },
-                4 => {
-                    textbox.queued_text = Some(TextBoxText {
-                        text: "PA: Just destroy all their antiques.".to_string(),
-                        speed: text_speed,
-                        auto: false,
-                        speaking: DisplayCharacter::Pa,
-                    });
-                },
-                5 => {
-                    cutscene_texture_state.mat = vec!(CutsceneTexture::MatIdle, 
-                                                     CutsceneTexture::MatTalk);
-                    textbox.queued_text = Some(TextBoxText {
-                        text: "MAT: I'm not sure I should do this...".to_string(),
-                        speed: text_speed,
-                        auto: false,
-                        speaking: DisplayCharacter::Mat,
-                    });
-                },
-                6 => {
-                    textbox.queued_text = Some(TextBoxText {
-                        text: "PA: That's the beauty, kid! You're not doing it, your bull is!".to_string(),
-                        speed: text_speed,
-                        auto: false,
-                        speaking: DisplayCharacter::Pa,
-                    });
-                },
-                7 => {
-                    textbox.queued_text = Some(TextBoxText {
-                        text: "PA: It's 100% legal!".to_string(),
-                        speed: text_speed,
-                        auto: false,
-                        speaking: DisplayCharacter::Pa,
-                    });
-                },
-                8 => {
-                    textbox.queued_text = Some(TextBoxText {
-                        text: "PA: Anymore questions?".to_string(),
-                        speed: text_speed,
-                        auto: false,
-                        speaking: DisplayCharacter::Pa,
-                    });
-                },
-                9 => {
-                    cutscene_texture_state.mat = vec!(CutsceneTexture::MatIdle, 
-                                                     CutsceneTexture::MatTalk);
-                    textbox.queued_text = Some(TextBoxText {
-                        text: "MAT: How come I can hear you right now?".to_string(),
-                        speed: text_speed,
-                        auto: false,
-                        speaking: DisplayCharacter::Mat,
-                    });
-                },
-                10 => {
-                    cutscene_texture_state.mat = vec!(CutsceneTexture::MatIdle); 
-                    textbox.queued_text = Some(TextBoxText {
-                        text: "PA: I gave you that earpiece, remember?".to_string(),
-                        speed: text_speed,
-                        auto: false,
-                        speaking: DisplayCharacter::Pa,
-                    });
-                },
-                11 => {
-                    cutscene_texture_state.mat = vec!(CutsceneTexture::MatIdle, 
-                                                     CutsceneTexture::MatTalk);
-                    textbox.queued_text = Some(TextBoxText {
-                        text: "MAT: Why's it so dark outside?".to_string(),
-                        speed: text_speed,
-                        auto: false,
-                        speaking: DisplayCharacter::Mat,
-                    });
-                },
-                12 => {
-                    cutscene_texture_state.mat = vec!(CutsceneTexture::MatIdle); 
-                    textbox.queued_text = Some(TextBoxText {
-                        text: "PA: Nevermind that! You're almost at the first shop.".to_string(),
-                        speed: text_speed,
-                        auto: false,
-                        speaking: DisplayCharacter::Pa,
-                    });
-                },
-                13 => {
-                    cutscene_texture_state.mat = vec!(CutsceneTexture::MatIdle); 
-                    textbox.queued_text = Some(TextBoxText {
-                        text: "PA: Remember, the best offensive is to be offensive!".to_string(),
-                        speed: text_speed,
-                        auto: false,
-                        speaking: DisplayCharacter::Pa,
-                    });
-                },
-                _ => {
-                    camera.translation = Vec3::new(game_camera::INGAME_CAMERA_X, 
-                                                   game_camera::INGAME_CAMERA_Y, 
-                                                   0.0);
-                    camera.rotation = Quat::from_axis_angle(game_camera::INGAME_CAMERA_ROTATION_AXIS, 
-                                                game_camera::INGAME_CAMERA_ROTATION_ANGLE);
-                    cutscene_state.target_camera_translation = None;
-                    cutscene_state.target_camera_rotation = None;
-                    cutscene_state.cutscene_index = 0;
-                    game_script_state.next();
-                    cutscene_state.waiting_on_input = false;
-                    assets_handler.load(AppState::LoadWorld, &mut game_assets, &game_state);
-                }
-            }
-        },
-        game_script::GameScript::LevelOneIntroCutscene => {
-            match cutscene_state.cutscene_index {
-                0 => {
-                    camera.translation = Vec3::new(22.5, 1.5, 0.0);
-                    camera.rotation = Quat::from_axis_angle(Vec3::new(-0.034182332, -0.9987495, -0.03648749), 1.5735247);
-                    cutscene_state.target_camera_translation = Some(Vec3::new(17.168844, 1.5, -0.0074485363));
-                    cutscene_state.target_camera_rotation = Some(Quat::from_axis_angle(Vec3::new(-0.03418233, -0.9987495, -0.03648749), 1.5735247));
-                    for entity in &players {
-                        let mut animation = animations.get_mut(entity).unwrap();
-                        animation.play(game_assets.matador_idle.clone_weak()).repeat();
-                        animation.set_speed(4.0);
-                    }
-                    for entity in &bulls {
-                        let mut animation = animations.get_mut(entity).unwrap();
-                        animation.play(game_assets.bull_idle.clone_weak()).repeat();
-                        animation.set_speed(2.0);
-                        animation.resume();
-                    }
-
-                    audio.stop_bgm();
-                    audio.play_bgm(&game_assets.level_one_bgm);
-                    textbox.queued_text = Some(TextBoxText {
-                        text: "Shopkeeper: Hello, welcome to 'First Plate'!".to_string(),
-                        speed: text_speed,
-                        auto: false,
-                        speaking: DisplayCharacter::Pa,
-                    });
-                },
-                1 => {
-                    for entity in &players {
-                        let mut animation = animations.get_mut(entity).unwrap();
-                        animation.play(game_assets.matador_idle.clone_weak()).repeat();
-                        animation.set_speed(4.0);
-                    }
-                    for entity in &bulls {
-                        let mut animation = animations.get_mut(entity).unwrap();
-                        animation.play(game_assets.bull_idle.clone_weak()).repeat();
-                        animation.set_speed(2.0);
-                        animation.resume();
-                    }
-                    textbox.queued_text = Some(TextBoxText {
-                        text: "Shopkeeper: Your one-stop shop for all your baseball antiques!".to_string(),
-                        speed: text_speed,
-                        auto: false,
-                        speaking: DisplayCharacter::Pa,
-                    });
-                },
-                2 => {
-                    for entity in &players {
-                        let mut animation = animations.get_mut(entity).unwrap();
-                        animation.play(game_assets.matador_idle.clone_weak()).repeat();
-                        animation.set_speed(4.0);
-                    }
-                    for entity in &bulls {
-                        let mut animation = animations.get_mut(entity).unwrap();
-                        animation.play(game_assets.bull_idle.clone_weak()).repeat();
-                        animation.set_speed(2.0);
-                        animation.resume();
-                    }
-                    cutscene_state.target_camera_translation = None;
-                    cutscene_state.target_camera_rotation = None;
-                    textbox.queued_text = Some(TextBoxText {
-                        text: "Shopkeeper: ... IS THAT A BULL!?".to_string(),
-                        speed: text_speed,
-                        auto: false,
-                        speaking: DisplayCharacter::Pa,
-                    });
-                },
-                3 => {
-                    cutscene_state.target_camera_translation = Some(Vec3::new(-10.231776, 1.5, -0.55118924));
-                    cutscene_state.target_camera_rotation = Some(Quat::from_axis_angle(Vec3::new(-0.05911547, 0.9965279, 0.0586311), 1.5660472));
-
-                    textbox.queued_text = Some(TextBoxText {
-                        text: "MAT: I think they see us.".to_string(),
-                        speed: text_speed,
-                        auto: false,
-                        speaking: DisplayCharacter::Mat,
-                    });
-                },
-                4 => {
-                    textbox.queued_text = Some(TextBoxText {
-                        text: "PA: That's ok, we have plenty of time!".to_string(),
-                        speed: text_speed,
-                        auto: false,
-                        speaking: DisplayCharacter::Pa,
-                    });
-                },
-                5 => {
-                    camera.translation = Vec3::new(17.168844, 1.5, -0.0074485363);
-                    camera.rotation = Quat::from_axis_angle(Vec3::new(-0.03418233, -0.9987495, -0.03648749), 1.5735247);
-                    cutscene_state.target_camera_translation = None;
-                    cutscene_state.target_camera_rotation = None;
-                    textbox.queued_text = Some(TextBoxText {
-                        text: "Shopkeeper: I'm calling the cops!".to_string(),
-                        speed: text_speed,
-                        auto: false,
-                        speaking: DisplayCharacter::Pa,
-                    });
-                },
-                6 => {
-                    camera.translation = Vec3::new(-10.231776, 1.5, -0.55118924);
-                    camera.rotation = Quat::from_axis_angle(Vec3::new(-0.05911547, 0.9965279, 0.0586311), 1.5660472);
-                    textbox.queued_text = Some(TextBoxText {
-                        text: "MAT: They're calling the cops.".to_string(),
-                        speed: text_speed,
-                        auto: false,
-                        speaking: DisplayCharacter::Mat,
-                    });
-                },
-                7 => {
-                    textbox.queued_text = Some(TextBoxText {
-                        text: "PA: You got two minutes kid, here's what you gotta do.".to_string(),
-                        speed: text_speed,
-                        auto: false,
-                        speaking: DisplayCharacter::Pa,
-                    });
-                },
-                8 => {
-                    textbox.queued_text = Some(TextBoxText {
-                        text: "PA: Use your action button to signal your bull to charge at you.".to_string(),
-                        speed: text_speed,
-                        auto: false,
-                        speaking: DisplayCharacter::Pa,
-                    });
-                },
-                9 => {
-                    textbox.queued_text = Some(TextBoxText {
-                        text: "PA: Then try to get it to knock all the antiques to the ground before time runs out!".to_string(),
-                        speed: text_speed,
-                        auto: false,
-                        speaking: DisplayCharacter::Pa,
-                    });
-                },
-                10 => {
-                    textbox.queued_text = Some(TextBoxText {
-                        text: "PA: Did that make sense?".to_string(),
-                        speed: text_speed,
-                        auto: false,
-                        speaking: DisplayCharacter::Pa,
-                    });
-                },
-                11 => {
-                    textbox.queued_text = Some(TextBoxText {
-                        text: "MAT: Yeah, Pa, thanks for explaining how to use my bull.".to_string(),
-                        speed: text_speed,
-                        auto: false,
-                        speaking: DisplayCharacter::Mat,
-                    });
-                },
-                12 => {
-                    textbox.queued_text = Some(TextBoxText {
-                        text: "MAT: I almost forgot I spent all my life training it.".to_string(),
-                        speed: text_speed,
-                        auto: false,
-                        speaking: DisplayCharacter::Mat,
-                    });
-                },
-                13 => {
-                    textbox.queued_text = Some(TextBoxText {
-                        text: "PA: No problem, kid, now GO!".to_string(),
-                        speed: text_speed,
-                        auto: false,
-                        speaking: DisplayCharacter::Pa,
-                    });
-                },
-                _ => {
-                    camera.translation = Vec3::new(game_camera::INGAME_CAMERA_X, 
-                                                   game_camera::INGAME_CAMERA_Y, 
-                                                   0.0);
-                    camera.rotation = Quat::from_axis_angle(game_camera::INGAME_CAMERA_ROTATION_AXIS, 
-                                                game_camera::INGAME_CAMERA_ROTATION_ANGLE);
-                    cutscene_state.cutscene_index = 0;
-                    game_script_state.next();
-                    cutscene_state.waiting_on_input = false;
-                    assets_handler.load(AppState::InGame, &mut game_assets, &game_state);
-                }
-            }
-        },
-        game_script::GameScript::LevelOnePostCutscene => {
-            match cutscene_state.cutscene_index {
-                0 => {
-                    audio.stop_bgm();
+    cutscene_state.waiting_on_input = true;
 
-                    camera.translation = Vec3::new(17.168844, 1.5, -0.0074485363);
-                    camera.rotation = Quat::from_axis_angle(Vec3::new(-0.03418233, -0.9987495, -0.03648749), 1.5735247);
-                    cutscene_state.target_camera_translation = None;
-                    cutscene_state.target_camera_rotation = None;
-                    textbox.queued_text = Some(TextBoxText {
-                        text: "Shopkeeper: ...wh..why??".to_string(),
-                        speed: text_speed,
-                        auto: false,
-                        speaking: DisplayCharacter::Pa,
-                    });
-                },
-                _ => {
-                    camera.translation = Vec3::new(game_camera::INGAME_CAMERA_X, 
-                                                   game_camera::INGAME_CAMERA_Y, 
-                                                   0.0);
-                    camera.rotation = Quat::from_axis_angle(game_camera::INGAME_CAMERA_ROTATION_AXIS, 
-                                                game_camera::INGAME_CAMERA_ROTATION_ANGLE);
-                    cutscene_state.cutscene_index = 0;
-                    game_script_state.next();
-                    cutscene_state.waiting_on_input = false;
-                    assets_handler.load(AppState::LoadWorld, &mut game_assets, &game_state);
-                }
-            }
-        },
-        game_script::GameScript::LevelTwoIntroCutscene => {
-            match cutscene_state.cutscene_index {
-                0 => {
-                    camera.translation = Vec3::new(22.5, 1.5, 0.0);
-                    camera.rotation = Quat::from_axis_angle(Vec3::new(-0.034182332, -0.9987495, -0.03648749), 1.5735247);
-                    cutscene_state.target_camera_translation = Some(Vec3::new(17.168844, 1.5, -0.0074485363));
-                    cutscene_state.target_camera_rotation = Some(Quat::from_axis_angle(Vec3::new(-0.03418233, -0.9987495, -0.03648749), 1.5735247));
-                    for entity in &players {
-                        let mut animation = animations.get_mut(entity).unwrap();
-                        animation.play(game_assets.matador_idle.clone_weak()).repeat();
-                        animation.set_speed(4.0);
-                    }
-                    for entity in &bulls {
-                        let mut animation = animations.get_mut(entity).unwrap();
-                        animation.play(game_assets.bull_idle.clone_weak()).repeat();
-                        animation.set_speed(2.0);
-                        animation.resume();
-                    }
+    println!("Cutscene: {:?} step {}", game_script_state.current, cutscene_state.cutscene_index);
+    if let Some(step) = script.steps.get(cutscene_state.cutscene_index) {
+        if let Some(pose) = &step.camera {
+            camera.translation = pose.translation;
+            camera.rotation = pose.rotation.quat();
+        }
+        match &step.target_translation {
+            CameraTarget::Keep => (),
+            CameraTarget::Clear => cutscene_state.target_camera_translation = None,
+            CameraTarget::MoveTo(translation) => cutscene_state.target_camera_translation = Some(*translation),
+        }
+        match &step.target_rotation {
+            CameraTarget::Keep => (),
+            CameraTarget::Clear => cutscene_state.target_camera_rotation = None,
+            CameraTarget::MoveTo(rotation) => cutscene_state.target_camera_rotation = Some(rotation.quat()),
+        }
+        cutscene_state.camera_speed = step.camera_speed;
 
-                    audio.stop_bgm();
-                    audio.play_bgm(&game_assets.level_two_bgm);
-                    textbox.queued_text = Some(TextBoxText {
-                        text: "Shopkeeper: Hello, welcome to 'West Spoon'.".to_string(),
-                        speed: text_speed,
-                        auto: false,
-                        speaking: DisplayCharacter::Pa,
-                    });
-                },
-                1 => {
-                    for entity in &players {
-                        let mut animation = animations.get_mut(entity).unwrap();
-                        animation.play(game_assets.matador_idle.clone_weak()).repeat();
-                        animation.set_speed(4.0);
-                    }
-                    for entity in &bulls {
-                        let mut animation = animations.get_mut(entity).unwrap();
-                        animation.play(game_assets.bull_idle.clone_weak()).repeat();
-                        animation.set_speed(2.0);
-                        animation.resume();
-                    }
-                    textbox.queued_text = Some(TextBoxText {
-                        text: "Shopkeeper: excuse me, is that a service bull?".to_string(),
-                        speed: text_speed,
-                        auto: false,
-                        speaking: DisplayCharacter::Pa,
-                    });
-                },
-                2 => {
-                    for entity in &players {
-                        let mut animation = animations.get_mut(entity).unwrap();
-                        animation.play(game_assets.matador_idle.clone_weak()).repeat();
-                        animation.set_speed(4.0);
-                    }
-                    for entity in &bulls {
-                        let mut animation = animations.get_mut(entity).unwrap();
-                        animation.play(game_assets.bull_idle.clone_weak()).repeat();
-                        animation.set_speed(2.0);
-                        animation.resume();
-                    }
-                    cutscene_state.target_camera_translation = Some(Vec3::new(-10.231776, 1.5, -0.55118924));
-                    cutscene_state.target_camera_rotation = Some(Quat::from_axis_angle(Vec3::new(-0.05911547, 0.9965279, 0.0586311), 1.5660472));
+        if step.stop_bgm {
+            audio.stop_bgm();
+        }
+        if step.stop_sfx {
+            audio.stop_sfx();
+        }
+        if let Some(bgm) = &step.bgm {
+            audio.play_bgm(&asset_server.load(bgm.as_str()));
+        }
+        if let Some(sfx) = &step.sfx_repeat {
+            audio.play_sfx_repeat(&asset_server.load(sfx.as_str()));
+        }
+        if let Some(sfx) = &step.sfx {
+            audio.play_sfx(&asset_server.load(sfx.as_str()));
+        }
 
-                    textbox.queued_text = Some(TextBoxText {
-                        text: "MAT: ...".to_string(),
-                        speed: text_speed,
-                        auto: false,
-                        speaking: DisplayCharacter::Mat,
-                    });
-                },
-                3 => {
-                    for entity in &players {
-                        let mut animation = animations.get_mut(entity).unwrap();
-                        animation.play(game_assets.matador_idle.clone_weak()).repeat();
-                        animation.set_speed(4.0);
-                    }
-                    for entity in &bulls {
-                        let mut animation = animations.get_mut(entity).unwrap();
-                        animation.play(game_assets.bull_idle.clone_weak()).repeat();
-                        animation.set_speed(2.0);
-                        animation.resume();
-                    }
-                    cutscene_state.target_camera_translation = Some(Vec3::new(-10.231776, 1.5, -0.55118924));
-                    cutscene_state.target_camera_rotation = Some(Quat::from_axis_angle(Vec3::new(-0.05911547, 0.9965279, 0.0586311), 1.5660472));
+        if let Some(mat) = &step.mat {
+            cutscene_texture_state.mat = mat.clone();
+        }
+        if let Some(pa) = &step.pa {
+            cutscene_texture_state.pa = pa.clone();
+        }
 
-                    textbox.queued_text = Some(TextBoxText {
-                        text: "MAT: ...yes.".to_string(),
-                        speed: text_speed,
-                        auto: false,
-                        speaking: DisplayCharacter::Mat,
-                    });
-                },
-                4 => {
-                    camera.translation = Vec3::new(17.168844, 1.5, -0.0074485363);
-                    camera.rotation = Quat::from_axis_angle(Vec3::new(-0.03418233, -0.9987495, -0.03648749), 1.5735247);
-                    cutscene_state.target_camera_translation = None;
-                    cutscene_state.target_camera_rotation = None;
-                    textbox.queued_text = Some(TextBoxText {
-                        text: "Shopkeeper: Oh, ok. Feel free to check out our new Appalachian Holiday collection.".to_string(),
-                        speed: text_speed,
-                        auto: false,
-                        speaking: DisplayCharacter::Pa,
-                    });
-                },
-                5 => {
-                    camera.translation = Vec3::new(-10.231776, 1.5, -0.55118924);
-                    camera.rotation = Quat::from_axis_angle(Vec3::new(-0.05911547, 0.9965279, 0.0586311), 1.5660472);
-                    textbox.queued_text = Some(TextBoxText {
-                        text: "MAT: ...S..sure.".to_string(),
-                        speed: text_speed,
-                        auto: false,
-                        speaking: DisplayCharacter::Mat,
-                    });
-                },
-                6 => {
-                    camera.translation = Vec3::new(17.168844, 1.5, -0.0074485363);
-                    camera.rotation = Quat::from_axis_angle(Vec3::new(-0.03418233, -0.9987495, -0.03648749), 1.5735247);
-                    cutscene_state.target_camera_translation = None;
-                    cutscene_state.target_camera_rotation = None;
-                    textbox.queued_text = Some(TextBoxText {
-                        text: "Shopkeeper: and don't worry if your bull breaks anything, I'll fix it no problem!".to_string(),
-                        speed: text_speed,
-                        auto: false,
-                        speaking: DisplayCharacter::Pa,
-                    });
-                },
-                7 => {
-                    camera.translation = Vec3::new(-10.231776, 1.5, -0.55118924);
-                    camera.rotation = Quat::from_axis_angle(Vec3::new(-0.05911547, 0.9965279, 0.0586311), 1.5660472);
-                    textbox.queued_text = Some(TextBoxText {
-                        text: "MAT: oh geez".to_string(),
-                        speed: text_speed,
-                        auto: false,
-                        speaking: DisplayCharacter::Mat,
-                    });
-                },
-                _ => {
-                    camera.translation = Vec3::new(game_camera::INGAME_CAMERA_X, 
-                                                   game_camera::INGAME_CAMERA_Y, 
-                                                   0.0);
-                    camera.rotation = Quat::from_axis_angle(game_camera::INGAME_CAMERA_ROTATION_AXIS, 
-                                                game_camera::INGAME_CAMERA_ROTATION_ANGLE);
-                    cutscene_state.cutscene_index = 0;
-                    game_script_state.next();
-                    cutscene_state.waiting_on_input = false;
-                    assets_handler.load(AppState::InGame, &mut game_assets, &game_state);
-                }
-            }
-        },
-        game_script::GameScript::LevelTwoPostCutscene => {
-            match cutscene_state.cutscene_index {
-                0 => {
-                    camera.translation = Vec3::new(17.168844, 1.5, -0.0074485363);
-                    camera.rotation = Quat::from_axis_angle(Vec3::new(-0.03418233, -0.9987495, -0.03648749), 1.5735247);
-                    cutscene_state.target_camera_translation = None;
-                    cutscene_state.target_camera_rotation = None;
-                    for entity in &players {
-                        let mut animation = animations.get_mut(entity).unwrap();
-                        animation.play(game_assets.matador_idle.clone_weak()).repeat();
-                        animation.set_speed(4.0);
-                    }
-                    for entity in &bulls {
-                        let mut animation = animations.get_mut(entity).unwrap();
-                        animation.play(game_assets.bull_idle.clone_weak()).repeat();
-                        animation.set_speed(2.0);
-                        animation.resume();
-                    }
-                    textbox.queued_text = Some(TextBoxText {
-                        text: "Shopkeeper: Thanks for stopping in! Have a nice day!".to_string(),
-                        speed: text_speed,
-                        auto: false,
-                        speaking: DisplayCharacter::Pa,
-                    });
-                },
-                1 => {
-                    for entity in &players {
-                        let mut animation = animations.get_mut(entity).unwrap();
-                        animation.play(game_assets.matador_idle.clone_weak()).repeat();
-                        animation.set_speed(4.0);
-                    }
-                    for entity in &bulls {
-                        let mut animation = animations.get_mut(entity).unwrap();
-                        animation.play(game_assets.bull_idle.clone_weak()).repeat();
-                        animation.set_speed(2.0);
-                        animation.resume();
-                    }
-                    camera.translation = Vec3::new(-10.231776, 1.5, -0.55118924);
-                    camera.rotation = Quat::from_axis_angle(Vec3::new(-0.05911547, 0.9965279, 0.0586311), 1.5660472);
-                    textbox.queued_text = Some(TextBoxText {
-                        text: "MAT: I'm not sure how effective that was...".to_string(),
-                        speed: text_speed,
-                        auto: false,
-                        speaking: DisplayCharacter::Mat,
-                    });
-                },
-                2 => {
-                    for entity in &players {
-                        let mut animation = animations.get_mut(entity).unwrap();
-                        animation.play(game_assets.matador_idle.clone_weak()).repeat();
-                        animation.set_speed(4.0);
-                    }
-                    for entity in &bulls {
-                        let mut animation = animations.get_mut(entity).unwrap();
-                        animation.play(game_assets.bull_idle.clone_weak()).repeat();
-                        animation.set_speed(2.0);
-                        animation.resume();
-                    }
-                    camera.translation = Vec3::new(-10.231776, 1.5, -0.55118924);
-                    camera.rotation = Quat::from_axis_angle(Vec3::new(-0.05911547, 0.9965279, 0.0586311), 1.5660472);
-                    textbox.queued_text = Some(TextBoxText {
-                        text: "PA: It's ok, I think you did some damage. Head to the next shop!".to_string(),
-                        speed: text_speed,
-                        auto: false,
-                        speaking: DisplayCharacter::Pa,
-                    });
-                },
-                _ => {
-                    camera.translation = Vec3::new(game_camera::INGAME_CAMERA_X, 
-                                                   game_camera::INGAME_CAMERA_Y, 
-                                                   0.0);
-                    camera.rotation = Quat::from_axis_angle(game_camera::INGAME_CAMERA_ROTATION_AXIS, 
-                                                game_camera::INGAME_CAMERA_ROTATION_ANGLE);
-                    cutscene_state.cutscene_index = 0;
-                    game_script_state.next();
-                    cutscene_state.waiting_on_input = false;
-                    assets_handler.load(AppState::LoadWorld, &mut game_assets, &game_state);
-                }
+        if let Some(player_animation) = step.player_animation {
+            for entity in &players {
+                let mut animation = animations.get_mut(entity).unwrap();
+                match player_animation {
+                    CharacterAnimation::Idle => animation.play(game_assets.matador_idle.clone_weak()).repeat(),
+                    CharacterAnimation::Run => animation.play(game_assets.matador_run.clone_weak()).repeat(),
+                };
+                animation.set_speed(4.0);
             }
-        },
-        game_script::GameScript::LevelThreeIntroCutscene => {
-            match cutscene_state.cutscene_index {
-                0 => {
-                    camera.translation = Vec3::new(22.5, 1.5, 0.0);
-                    camera.rotation = Quat::from_axis_angle(Vec3::new(-0.034182332, -0.9987495, -0.03648749), 1.5735247);
-                    cutscene_state.target_camera_translation = Some(Vec3::new(4.868683, 1.422154, -0.04496868));
-                    cutscene_state.target_camera_rotation = Some(Quat::from_axis_angle(Vec3::new(0.018233472, 0.9996569, 0.018801434), 4.681377));
-
-                    for entity in &players {
-                        let mut animation = animations.get_mut(entity).unwrap();
-                        animation.play(game_assets.matador_idle.clone_weak()).repeat();
-                        animation.set_speed(4.0);
-                    }
-                    for entity in &bulls {
-                        let mut animation = animations.get_mut(entity).unwrap();
-                        animation.play(game_assets.bull_idle.clone_weak()).repeat();
-                        animation.set_speed(2.0);
-                        animation.resume();
-                    }
-
-                    audio.stop_bgm();
-                    audio.play_bgm(&game_assets.level_three_bgm);
-
-                    textbox.queued_text = Some(TextBoxText {
-                        text: "Shopkeeper: Howdy and welcome to 'Dishes and Fishes'!".to_string(),
-                        speed: text_speed,
-                        auto: false,
-                        speaking: DisplayCharacter::Pa,
-                    });
-                },
-                1 => {
-                    for entity in &players {
-                        let mut animation = animations.get_mut(entity).unwrap();
-                        animation.play(game_assets.matador_idle.clone_weak()).repeat();
-                        animation.set_speed(4.0);
-                    }
-                    for entity in &bulls {
-                        let mut animation = animations.get_mut(entity).unwrap();
-                        animation.play(game_assets.bull_idle.clone_weak()).repeat();
-                        animation.set_speed(2.0);
-                        animation.resume();
-                    }
-                    textbox.queued_text = Some(TextBoxText {
-                        text: "Shopkeeper: no, we're not a restaurant, we actually sell fish and novelty plates.".to_string(),
-                        speed: text_speed,
-                        auto: false,
-                        speaking: DisplayCharacter::Pa,
-                    });
-                },
-                2 => {
-                    for entity in &players {
-                        let mut animation = animations.get_mut(entity).unwrap();
-                        animation.play(game_assets.matador_idle.clone_weak()).repeat();
-                        animation.set_speed(4.0);
-                    }
-                    for entity in &bulls {
-                        let mut animation = animations.get_mut(entity).unwrap();
-                        animation.play(game_assets.bull_idle.clone_weak()).repeat();
-                        animation.set_speed(2.0);
-                        animation.resume();
-                    }
-                    textbox.queued_text = Some(TextBoxText {
-                        text: "Shopkeeper: we also have a prized red herring on display but it is not for sale.".to_string(),
-                        speed: text_speed,
-                        auto: false,
-                        speaking: DisplayCharacter::Pa,
-                    });
-                },
-                3 => {
-                    for entity in &players {
-                        let mut animation = animations.get_mut(entity).unwrap();
-                        animation.play(game_assets.matador_idle.clone_weak()).repeat();
-                        animation.set_speed(4.0);
-                    }
-                    for entity in &bulls {
-                        let mut animation = animations.get_mut(entity).unwrap();
-                        animation.play(game_assets.bull_idle.clone_weak()).repeat();
-                        animation.set_speed(2.0);
-                        animation.resume();
-                    }
-                    textbox.queued_text = Some(TextBoxText {
-                        text: "Shopkeeper: please be careful around the fish bowls".to_string(),
-                        speed: text_speed,
-                        auto: false,
-                        speaking: DisplayCharacter::Pa,
-                    });
-                },
-                4 => {
-                    for entity in &players {
-                        let mut animation = animations.get_mut(entity).unwrap();
-                        animation.play(game_assets.matador_idle.clone_weak()).repeat();
-                        animation.set_speed(4.0);
-                    }
-                    for entity in &bulls {
-                        let mut animation = animations.get_mut(entity).unwrap();
-                        animation.play(game_assets.bull_idle.clone_weak()).repeat();
-                        animation.set_speed(2.0);
-                        animation.resume();
-                    }
-                    textbox.queued_text = Some(TextBoxText {
-                        text: "Shopkeeper: ...or else!".to_string(),
-                        speed: text_speed,
-                        auto: false,
-                        speaking: DisplayCharacter::Pa,
-                    });
-                },
-                5 => {
-                    for entity in &players {
-                        let mut animation = animations.get_mut(entity).unwrap();
-                        animation.play(game_assets.matador_idle.clone_weak()).repeat();
-                        animation.set_speed(4.0);
-                    }
-                    for entity in &bulls {
-                        let mut animation = animations.get_mut(entity).unwrap();
-                        animation.play(game_assets.bull_idle.clone_weak()).repeat();
-                        animation.set_speed(2.0);
-                        animation.resume();
-                    }
-                    cutscene_state.target_camera_translation = Some(Vec3::new(-10.231776, 1.5, -0.55118924));
-                    cutscene_state.target_camera_rotation = Some(Quat::from_axis_angle(Vec3::new(-0.05911547, 0.9965279, 0.0586311), 1.5660472));
-
-                    textbox.queued_text = Some(TextBoxText {
-                        text: "MAT: ...".to_string(),
-                        speed: text_speed,
-                        auto: false,
-                        speaking: DisplayCharacter::Mat,
-                    });
-                },
-                6 => {
-                    for entity in &players {
-                        let mut animation = animations.get_mut(entity).unwrap();
-                        animation.play(game_assets.matador_idle.clone_weak()).repeat();
-                        animation.set_speed(4.0);
-                    }
-                    for entity in &bulls {
-                        let mut animation = animations.get_mut(entity).unwrap();
-                        animation.play(game_assets.bull_idle.clone_weak()).repeat();
-                        animation.set_speed(2.0);
-                        animation.resume();
-                    }
-                    cutscene_state.target_camera_translation = Some(Vec3::new(-10.231776, 1.5, -0.55118924));
-                    cutscene_state.target_camera_rotation = Some(Quat::from_axis_angle(Vec3::new(-0.05911547, 0.9965279, 0.0586311), 1.5660472));
-
-                    textbox.queued_text = Some(TextBoxText {
-                        text: "MAT: what a strange place".to_string(),
-                        speed: text_speed,
-                        auto: false,
-                        speaking: DisplayCharacter::Mat,
-                    });
-                },
-                7 => {
-                    for entity in &players {
-                        let mut animation = animations.get_mut(entity).unwrap();
-                        animation.play(game_assets.matador_idle.clone_weak()).repeat();
-                        animation.set_speed(4.0);
-                    }
-                    for entity in &bulls {
-                        let mut animation = animations.get_mut(entity).unwrap();
+        }
+        if let Some(bull_animation) = step.bull_animation {
+            for entity in &bulls {
+                let mut animation = animations.get_mut(entity).unwrap();
+                match bull_animation {
+                    CharacterAnimation::Idle => {
                         animation.play(game_assets.bull_idle.clone_weak()).repeat();
                         animation.set_speed(2.0);
-                        animation.resume();
-                    }
-
-                    textbox.queued_text = Some(TextBoxText {
-                        text: "PA: what are you doing!? Get to work, kid!".to_string(),
-                        speed: text_speed,
-                        auto: false,
-                        speaking: DisplayCharacter::Pa,
-                    });
-                },
-                _ => {
-                    camera.translation = Vec3::new(game_camera::INGAME_CAMERA_X, 
-                                                   game_camera::INGAME_CAMERA_Y, 
-                                                   0.0);
-                    camera.rotation = Quat::from_axis_angle(game_camera::INGAME_CAMERA_ROTATION_AXIS, 
-                                                game_camera::INGAME_CAMERA_ROTATION_ANGLE);
-                    cutscene_state.cutscene_index = 0;
-                    game_script_state.next();
-                    cutscene_state.waiting_on_input = false;
-                    assets_handler.load(AppState::InGame, &mut game_assets, &game_state);
-                }
+                    },
+                    CharacterAnimation::Run => {
+                        animation.play(game_assets.bull_run.clone_weak()).repeat();
+                        animation.set_speed(3.0);
+                    },
+                };
+                animation.resume();
             }
-        },
-        game_script::GameScript::LevelThreePostCutscene => {
-            match cutscene_state.cutscene_index {
-                0 => {
-                    audio.stop_bgm();
+        }
 
-                    for entity in &players {
-                        let mut animation = animations.get_mut(entity).unwrap();
-                        animation.play(game_assets.matador_idle.clone_weak()).repeat();
-                        animation.set_speed(4.0);
-                    }
-                    for entity in &bulls {
-                        let mut animation = animations.get_mut(entity).unwrap();
-                        animation.play(game_assets.bull_idle.clone_weak()).repeat();
-                        animation.set_speed(2.0);
-                        animation.resume();
-                    }
-                    camera.translation = Vec3::new(4.868683, 1.422154, -0.04496868);
-                    camera.rotation = Quat::from_axis_angle(Vec3::new(0.018233472, 0.9996569, 0.018801434), 4.681377);
-                    cutscene_state.target_camera_translation = None;
-                    cutscene_state.target_camera_rotation = None;
-                    for e in &pointlights {
-                        commands.entity(e).insert(ingame::FishLight {
-                            is_siren: true,
-                            is_blue: true,
-                            cooldown: 1.0,
-                        });
-                    }
-                    textbox.queued_text = Some(TextBoxText {
-                        text: "Shopkeeper: Over here, officer!!".to_string(),
-                        speed: text_speed,
-                        auto: false,
-                        speaking: DisplayCharacter::Pa,
-                    });
-                },
-                1 => {
-                    for e in &pointlights {
-                        commands.entity(e).insert(ingame::FishLight {
-                            is_siren: true,
-                            is_blue: true,
-                            cooldown: 1.0,
-                        });
-                    }
-                    for entity in &players {
-                        let mut animation = animations.get_mut(entity).unwrap();
-                        animation.play(game_assets.matador_idle.clone_weak()).repeat();
-                        animation.set_speed(4.0);
-                    }
-                    for entity in &bulls {
-                        let mut animation = animations.get_mut(entity).unwrap();
-                        animation.play(game_assets.bull_idle.clone_weak()).repeat();
-                        animation.set_speed(2.0);
-                        animation.resume();
-                    }
-                    camera.translation = Vec3::new(-10.231776, 1.5, -0.55118924);
-                    camera.rotation = Quat::from_axis_angle(Vec3::new(-0.05911547, 0.9965279, 0.0586311), 1.5660472);
-                    textbox.queued_text = Some(TextBoxText {
-                        text: "MAT: Ahhh! The cops caught up to us!".to_string(),
-                        speed: text_speed,
-                        auto: false,
-                        speaking: DisplayCharacter::Mat,
-                    });
-                },
-                2 => {
-                    for entity in &players {
-                        let mut animation = animations.get_mut(entity).unwrap();
-                        animation.play(game_assets.matador_idle.clone_weak()).repeat();
-                        animation.set_speed(4.0);
-                    }
-                    for entity in &bulls {
-                        let mut animation = animations.get_mut(entity).unwrap();
-                        animation.play(game_assets.bull_idle.clone_weak()).repeat();
-                        animation.set_speed(2.0);
-                        animation.resume();
-                    }
-                    camera.translation = Vec3::new(-10.231776, 1.5, -0.55118924);
-                    camera.rotation = Quat::from_axis_angle(Vec3::new(-0.05911547, 0.9965279, 0.0586311), 1.5660472);
-                    textbox.queued_text = Some(TextBoxText {
-                        text: "PA: it's ok! You didn't do anything wrong!".to_string(),
-                        speed: text_speed,
-                        auto: false,
-                        speaking: DisplayCharacter::Pa,
-                    });
-                },
-                3 => {
-                    for entity in &players {
-                        let mut animation = animations.get_mut(entity).unwrap();
-                        animation.play(game_assets.matador_idle.clone_weak()).repeat();
-                        animation.set_speed(4.0);
-                    }
-                    for entity in &bulls {
-                        let mut animation = animations.get_mut(entity).unwrap();
-                        animation.play(game_assets.bull_idle.clone_weak()).repeat();
-                        animation.set_speed(2.0);
-                        animation.resume();
-                    }
-                    camera.translation = Vec3::new(-10.231776, 1.5, -0.55118924);
-                    camera.rotation = Quat::from_axis_angle(Vec3::new(-0.05911547, 0.9965279, 0.0586311), 1.5660472);
-                    textbox.queued_text = Some(TextBoxText {
-                        text: "MAT: They say I'm responsible for the bull!".to_string(),
-                        speed: text_speed,
-                        auto: false,
-                        speaking: DisplayCharacter::Mat,
-                    });
-                },
-                4 => {
-                    camera.translation = Vec3::new(-10.231776, 1.5, -0.55118924);
-                    camera.rotation = Quat::from_axis_angle(Vec3::new(-0.05911547, 0.9965279, 0.0586311), 1.5660472);
-                    textbox.queued_text = Some(TextBoxText {
-                        text: "PA: WHAT!?".to_string(),
-                        speed: text_speed,
-                        auto: false,
-                        speaking: DisplayCharacter::Pa,
-                    });
-                },
-                5 => {
-                    camera.translation = Vec3::new(-10.231776, 1.5, -0.55118924);
-                    camera.rotation = Quat::from_axis_angle(Vec3::new(-0.05911547, 0.9965279, 0.0586311), 1.5660472);
-                    textbox.queued_text = Some(TextBoxText {
-                        text: "MAT: They're going to arrest me!".to_string(),
-                        speed: text_speed,
-                        auto: false,
-                        speaking: DisplayCharacter::Mat,
-                    });
-                },
-                6 => {
-                    camera.translation = Vec3::new(-10.231776, 1.5, -0.55118924);
-                    camera.rotation = Quat::from_axis_angle(Vec3::new(-0.05911547, 0.9965279, 0.0586311), 1.5660472);
-                    textbox.queued_text = Some(TextBoxText {
-                        text: "PA: What a load of b-".to_string(),
-                        speed: text_speed,
-                        auto: true,
-                        speaking: DisplayCharacter::Pa,
-                    });
-                },
-                _ => {
-                    camera.translation = Vec3::new(game_camera::INGAME_CAMERA_X, 
-                                                   game_camera::INGAME_CAMERA_Y, 
-                                                   0.0);
-                    camera.rotation = Quat::from_axis_angle(game_camera::INGAME_CAMERA_ROTATION_AXIS, 
-                                                game_camera::INGAME_CAMERA_ROTATION_ANGLE);
-                    cutscene_state.cutscene_index = 0;
-                    game_script_state.next();
-                    cutscene_state.waiting_on_input = false;
-                    assets_handler.load(AppState::LoadWorld, &mut game_assets, &game_state);
-                }
-            }
-        },
-        game_script::GameScript::LevelFourIntroCutscene => {
-            match cutscene_state.cutscene_index {
-                0 => {
-                    camera.translation = Vec3::new(22.5, 1.5, 0.0);
-                    camera.rotation = Quat::from_axis_angle(Vec3::new(-0.034182332, -0.9987495, -0.03648749), 1.5735247);
-                    audio.stop_bgm();
-                    cutscene_state.target_camera_translation = Some((Vec3::new(19.3, 1.5, 0.0)));
-                    textbox.queued_text = Some(TextBoxText {
-                        text: "Level Four Intro Cutscene!".to_string(),
-                        speed: text_speed,
-                        auto: false,
-                        speaking: DisplayCharacter::Mat,
-                    });
-                },
-                _ => {
-                    camera.translation = Vec3::new(game_camera::INGAME_CAMERA_X, 
-                                                   game_camera::INGAME_CAMERA_Y, 
-                                                   0.0);
-                    camera.rotation = Quat::from_axis_angle(game_camera::INGAME_CAMERA_ROTATION_AXIS, 
-                                                game_camera::INGAME_CAMERA_ROTATION_ANGLE);
-                    cutscene_state.cutscene_index = 0;
-                    game_script_state.next();
-                    cutscene_state.waiting_on_input = false;
-                    assets_handler.load(AppState::InGame, &mut game_assets, &game_state);
-                }
-            }
-        },
-        game_script::GameScript::LevelFourPostCutscene => {
-            match cutscene_state.cutscene_index {
-                0 => {
-                    camera.translation = Vec3::new(22.5, 1.5, 0.0);
-                    camera.rotation = Quat::from_axis_angle(Vec3::new(-0.034182332, -0.9987495, -0.03648749), 1.5735247);
-                    audio.stop_bgm();
-                    cutscene_state.target_camera_translation = Some((Vec3::new(19.3, 1.5, 0.0)));
-                    textbox.queued_text = Some(TextBoxText {
-                        text: "Level Four POST Cutscene!".to_string(),
-                        speed: text_speed,
-                        auto: false,
-                        speaking: DisplayCharacter::Mat,
-                    });
-                },
-                _ => {
-                    camera.translation = Vec3::new(game_camera::INGAME_CAMERA_X, 
-                                                   game_camera::INGAME_CAMERA_Y, 
-                                                   0.0);
-                    camera.rotation = Quat::from_axis_angle(game_camera::INGAME_CAMERA_ROTATION_AXIS, 
-                                                game_camera::INGAME_CAMERA_ROTATION_ANGLE);
-                    cutscene_state.cutscene_index = 0;
-                    game_script_state.next();
-                    cutscene_state.waiting_on_input = false;
-                    assets_handler.load(AppState::LoadWorld, &mut game_assets, &game_state);
-                }
-            }
-        },
-        game_script::GameScript::LevelFiveIntroCutscene => {
-            match cutscene_state.cutscene_index {
-                0 => {
-                    camera.translation = Vec3::new(22.5, 1.5, 0.0);
-                    camera.rotation = Quat::from_axis_angle(Vec3::new(-0.034182332, -0.9987495, -0.03648749), 1.5735247);
-                    audio.stop_bgm();
-                    cutscene_state.target_camera_translation = Some((Vec3::new(19.3, 1.5, 0.0)));
-                    textbox.queued_text = Some(TextBoxText {
-                        text: "Level Five Intro Cutscene!".to_string(),
-                        speed: text_speed,
-                        auto: false,
-                        speaking: DisplayCharacter::Mat,
-                    });
-                },
-                _ => {
-                    camera.translation = Vec3::new(game_camera::INGAME_CAMERA_X, 
-                                                   game_camera::INGAME_CAMERA_Y, 
-                                                   0.0);
-                    camera.rotation = Quat::from_axis_angle(game_camera::INGAME_CAMERA_ROTATION_AXIS, 
-                                                game_camera::INGAME_CAMERA_ROTATION_ANGLE);
-                    cutscene_state.cutscene_index = 0;
-                    game_script_state.next();
-                    cutscene_state.waiting_on_input = false;
-                    assets_handler.load(AppState::InGame, &mut game_assets, &game_state);
-                }
-            }
-        },
-        game_script::GameScript::LevelFivePostCutscene => {
-            match cutscene_state.cutscene_index {
-                0 => {
-                    camera.translation = Vec3::new(22.5, 1.5, 0.0);
-                    camera.rotation = Quat::from_axis_angle(Vec3::new(-0.034182332, -0.9987495, -0.03648749), 1.5735247);
-                    audio.stop_bgm();
-                    cutscene_state.target_camera_translation = Some((Vec3::new(19.3, 1.5, 0.0)));
-                    textbox.queued_text = Some(TextBoxText {
-                        text: "Level Five POST Cutscene!".to_string(),
-                        speed: text_speed,
-                        auto: false,
-                        speaking: DisplayCharacter::Mat,
-                    });
-                },
-                _ => {
-                    camera.translation = Vec3::new(game_camera::INGAME_CAMERA_X, 
-                                                   game_camera::INGAME_CAMERA_Y, 
-                                                   0.0);
-                    camera.rotation = Quat::from_axis_angle(game_camera::INGAME_CAMERA_ROTATION_AXIS, 
-                                                game_camera::INGAME_CAMERA_ROTATION_ANGLE);
-                    cutscene_state.cutscene_index = 0;
-                    game_script_state.next();
-                    cutscene_state.waiting_on_input = false;
-                    assets_handler.load(AppState::LoadWorld, &mut game_assets, &game_state);
-                }
+        if step.sirens {
+            for e in &pointlights {
+                commands.entity(e).insert(ingame::FishLight {
+                    is_siren: true,
+                    is_blue: true,
+                    cooldown: 1.0,
+                });
             }
-        },
-        game_script::GameScript::EndCutscene => {
-            match cutscene_state.cutscene_index {
-                0 => {
-                    println!("Cutscene: 0 in Outro");
-
+        }
 
-                    cutscene_state.target_camera_translation = Some(Vec3::new(14.423876, 1.6476835, 21.160599));
-                    cutscene_state.target_camera_rotation = Some(Quat::from_axis_angle(Vec3::new(-0.076007806, -0.9942875, -0.07493414), 1.5622988));
+        if let Some(text) = &step.text {
+            textbox.queued_text = Some(text.clone());
+        }
 
-                    camera.translation = Vec3::new(20.253891, 1.6476835, 21.739094);
-                    camera.rotation = Quat::from_axis_angle(Vec3::new(-0.076007806, -0.9942875, -0.07493414), 1.5622988);
-                    cutscene_state.camera_speed = 1.0; 
-                    audio.stop_bgm();
-                    audio.play_bgm(&game_assets.intro_bgm);
-//                  cutscene_texture_state.pa = vec!(CutsceneTexture::PaIdle, 
-//                                                   CutsceneTexture::PaTalk);
-                    cutscene_texture_state.mat = vec!(CutsceneTexture::MatIdle, 
-                                                     CutsceneTexture::MatTalk);
-                    textbox.queued_text = Some(TextBoxText {
-                        text: "MAT: Whelp...".to_string(),
-                        speed: text_speed,
-                        auto: false,
-                        speaking: DisplayCharacter::Mat,
-                    });
-                },
-                1 => {
-                    cutscene_texture_state.mat = vec!(CutsceneTexture::MatIdle);
-                    cutscene_texture_state.pa = vec!(CutsceneTexture::PaIdle, 
-                                                     CutsceneTexture::PaTalk);
-                    textbox.queued_text = Some(TextBoxText {
-                        text: "PA: Yeah that was 100% on me, kid".to_string(),
-                        speed: text_speed,
-                        auto: false,
-                        speaking: DisplayCharacter::Pa,
-                    });
-                },
-                2 => {
-                    cutscene_texture_state.mat = vec!(CutsceneTexture::MatIdle, 
-                                                     CutsceneTexture::MatTalk);
-                    cutscene_texture_state.pa = vec!(CutsceneTexture::PaIdle); 
-                    textbox.queued_text = Some(TextBoxText {
-                        text: "MAT: I'm just surprised they sent all three of us to jail.".to_string(),
-                        speed: text_speed,
-                        auto: false,
-                        speaking: DisplayCharacter::Mat,
-                    });
-                },
-                3 => {
-                    cutscene_texture_state.mat = vec!(CutsceneTexture::MatIdle);
-                    cutscene_texture_state.pa = vec!(CutsceneTexture::PaIdle, 
-                                                     CutsceneTexture::PaTalk);
-                    textbox.queued_text = Some(TextBoxText {
-                        text: "PA: Yeah, I guess it's true what they say...".to_string(),
-                        speed: text_speed,
-                        auto: false,
-                        speaking: DisplayCharacter::Pa,
-                    });
-                },
-                4 => {
-                    cutscene_texture_state.mat = vec!(CutsceneTexture::MatIdle);
-                    cutscene_texture_state.pa = vec!(CutsceneTexture::PaIdle, 
-                                                     CutsceneTexture::PaTalk);
-                    textbox.queued_text = Some(TextBoxText {
-                        text: "PA: There's no 'I' in 'Guilty'.".to_string(),
-                        speed: text_speed,
-                        auto: false,
-                        speaking: DisplayCharacter::Pa,
-                    });
-                },
-                5 => {
-                    cutscene_texture_state.mat = vec!(CutsceneTexture::MatIdle);
-                    cutscene_texture_state.pa = vec!(CutsceneTexture::PaIdle);
-                    textbox.queued_text = Some(TextBoxText {
-                        text: "MAT: ...".to_string(),
-                        speed: text_speed,
-                        auto: false,
-                        speaking: DisplayCharacter::None,
-                    });
-                },
-                6 => {
-                    cutscene_texture_state.mat = vec!(CutsceneTexture::MatIdle, 
-                                                     CutsceneTexture::MatTalk);
-                    cutscene_texture_state.pa = vec!(CutsceneTexture::PaLook); 
-                    textbox.queued_text = Some(TextBoxText {
-                        text: "MAT: Please stop.".to_string(),
-                        speed: text_speed,
-                        auto: false,
-                        speaking: DisplayCharacter::Mat,
-                    });
-                },
-                _ => {
-                    camera.translation = Vec3::new(game_camera::INGAME_CAMERA_X, 
-                                                   game_camera::INGAME_CAMERA_Y, 
-                                                   0.0);
-                    camera.rotation = Quat::from_axis_angle(game_camera::INGAME_CAMERA_ROTATION_AXIS, 
-                                                game_camera::INGAME_CAMERA_ROTATION_ANGLE);
-                    cutscene_state.target_camera_translation = None;
-                    cutscene_state.target_camera_rotation = None;
-                    cutscene_state.cutscene_index = 0;
-                    game_script_state.next();
-                    cutscene_state.waiting_on_input = false;
-                    assets_handler.load(AppState::TitleScreen, &mut game_assets, &game_state);
+        if step.advance {
+            cutscene_state.input_cooldown = 0.5;
+            cutscene_state.waiting_on_input = false;
+            cutscene_state.cutscene_index += 1;
+            // clear out existing text
+            for children in text_container.iter() {
+                for entity in children.iter() {
+                    commands.get_or_spawn(*entity).despawn_recursive();
                 }
             }
-        },
-        _ => {
-            println!("uhh not a cutscene???");
+        }
+    } else {
+        println!("Cutscene: Done with {:?}", game_script_state.current);
+        camera.translation = Vec3::new(game_camera::INGAME_CAMERA_X, 
+                                       game_camera::INGAME_CAMERA_Y, 
+                                       0.0);
+        camera.rotation = Quat::from_axis_angle(game_camera::INGAME_CAMERA_ROTATION_AXIS, 
+                                    game_camera::INGAME_CAMERA_ROTATION_ANGLE);
+        cutscene_state.target_camera_translation = None;
+        cutscene_state.target_camera_rotation = None;
+        cutscene_state.cutscene_index = 0;
+        if script.finish.stop_bgm {
+            audio.stop_bgm();
+        }
+        if script.finish.stop_sfx {
+            audio.stop_sfx();
+        }
+        cutscene_state.waiting_on_input = false;
+        if let Some(campaign) = campaigns.get(&game_assets.campaign) {
+            let next_state = game_script_state.next(campaign);
+            assets_handler.load(next_state, &mut game_assets, &game_state);
         }
     }
 }
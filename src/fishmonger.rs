@@ -2,7 +2,7 @@ use bevy::prelude::*;
 use bevy::ecs::system::EntityCommands;
 use bevy::gltf::Gltf;
 use crate::{
-    AppState, assets::GameAssets, game_state, groups, ZeroSignum, dust, score, player, follow_text,
+    AppState, assets::GameAssets, game_state, groups, navmesh, ZeroSignum, dust, score, player, follow_text,
 };
 use std::collections::HashMap;
 use std::str::FromStr;
@@ -42,6 +42,7 @@ struct FishMonger {
     pub current_animation: Handle<AnimationClip>,
     pub target: Option::<Vec3>,
     pub initial_position: Option::<Vec3>,
+    pub path: navmesh::Path,
 }
 
 impl Default for FishMonger {
@@ -57,6 +58,7 @@ impl Default for FishMonger {
             dust_cooldown: 0.0,
             target: None,
             initial_position: None,
+            path: navmesh::Path::default(),
         }
     }
 }
@@ -90,6 +92,7 @@ fn move_fishmongers( //, &mut Handle<Gltf>
     mut game_state: ResMut<game_state::GameState>,
     mut player: Query<&Transform, (With<player::Player>, Without<FishMonger>)>,
     time: Res<Time>,
+    navmesh: Res<navmesh::NavMesh>,
     game_assets: ResMut<GameAssets>,
     mut restore_group_event_writer: EventWriter<groups::RestoreGroupEvent>,
     mut dust_spawn_event_writer: EventWriter<dust::DustSpawnEvent>,
@@ -158,6 +161,8 @@ fn move_fishmongers( //, &mut Handle<Gltf>
         if let Some(target) = monger.target {
             let target = Vec3::new(target.x, 0.0, target.z);
             //println!("Moving to target? {} to {}", monger_transform.translation, target);
+            let translation = monger_transform.translation;
+            let target = monger.path.steer(&navmesh, translation, target, time.delta_seconds());
             let direction = target - monger_transform.translation;
             let acceleration = Vec3::from(direction);
 
@@ -1,84 +1,159 @@
 use bevy::prelude::*;
+use bevy::asset::{AssetLoader, LoadContext, LoadedAsset};
+use bevy::reflect::TypeUuid;
+use bevy::utils::BoxedFuture;
+use serde::Deserialize;
+use std::collections::HashSet;
 use crate::{
-    asset_loading, assets::GameAssets, game_state, AppState, 
+    asset_loading, assets::GameAssets, bull, bull_mind, game_state, AppState,
 };
 
 pub struct GameScriptPlugin;
 impl Plugin for GameScriptPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<GameScriptState>()
+           .add_asset::<Campaign>()
+           .init_asset_loader::<CampaignLoader>()
            .add_system_set(SystemSet::on_update(AppState::LoadWorld).with_system(load_state));
     }
 }
 
-#[derive(Debug)]
-pub enum GameScript {
-    IntroCutscene,
-    EndCutscene,
-    PreLevelOneCutscene,
-    LevelOneIntroCutscene,
-    LevelOne,
-    LevelOnePostCutscene,
-
-    LevelTwoIntroCutscene,
-    LevelTwo,
-    LevelTwoPostCutscene,
-
-    LevelThreeIntroCutscene,
-    LevelThree,
-    LevelThreePostCutscene,
-
-    LevelFourIntroCutscene,
-    LevelFour,
-    LevelFourPostCutscene,
-
-    LevelFiveIntroCutscene,
-    LevelFive,
-    LevelFivePostCutscene,
+// the order of the game lives in assets/main.campaign.ron, each chapter is
+// either a cutscene or a playable level and names the chapter after it
+#[derive(Deserialize, TypeUuid)]
+#[uuid = "3f4d9b0e-6f2a-4c55-9a55-0f3c8d1e7b21"]
+pub struct Campaign {
+    pub start: String,
+    pub chapters: Vec::<Chapter>,
 }
 
-#[derive(Resource)]
-pub struct GameScriptState {
-    pub current: GameScript,
-}
+impl Campaign {
+    pub fn chapter(&self, id: &str) -> Option::<&Chapter> {
+        self.chapters.iter().find(|c| c.id == id)
+    }
 
-impl Default for GameScriptState {
-    fn default() -> Self {
-        GameScriptState {
-            current: GameScript::IntroCutscene,
+    // playable chapters in the order the story reaches them
+    pub fn levels(&self) -> Vec::<&Chapter> {
+        let mut levels = vec!();
+        let mut visited = HashSet::<&str>::new();
+        let mut current = self.chapter(&self.start);
+        while let Some(chapter) = current {
+            // the campaign is allowed to loop back around
+            if !visited.insert(chapter.id.as_str()) {
+                break;
+            }
+            if chapter.is_level() {
+                levels.push(chapter);
+            }
+            current = chapter.next.as_ref().and_then(|next| self.chapter(next));
         }
+        levels
+    }
+
+    pub fn next_level(&self, level: &str) -> Option::<&Chapter> {
+        let levels = self.levels();
+        levels.iter()
+              .position(|chapter| chapter.id == level)
+              .and_then(|i| levels.get(i + 1))
+              .copied()
+    }
+
+    // the cutscene that leads into a level, or the level itself if it doesn't have one
+    pub fn level_entry<'a>(&'a self, level: &'a str) -> &'a str {
+        self.chapters.iter()
+                     .find(|c| !c.is_level() && c.next.as_deref() == Some(level))
+                     .map(|c| c.id.as_str())
+                     .unwrap_or(level)
+    }
+}
+
+#[derive(Deserialize, Clone)]
+pub struct Chapter {
+    pub id: String,
+    pub cutscene: Option::<String>,
+    pub level: String,
+    #[serde(default)]
+    pub bgm: Option::<String>,
+    #[serde(default = "default_ambient_light")]
+    pub ambient_light: f32,
+    #[serde(default)]
+    pub sun: bool,
+    #[serde(default = "default_time_limit")]
+    pub time_limit: f32,
+    #[serde(default)]
+    pub bull: bull_mind::BullWeights,
+    // for "bull" nodes that don't name a kind of their own
+    #[serde(default)]
+    pub bull_kind: bull::BullKind,
+    // no next chapter means the campaign is over
+    #[serde(default)]
+    pub next: Option::<String>,
+}
+
+impl Chapter {
+    pub fn is_level(&self) -> bool {
+        self.cutscene.is_none()
     }
 }
 
+fn default_ambient_light() -> f32 {
+    0.50
+}
+
+fn default_time_limit() -> f32 {
+    90.0
+}
+
+#[derive(Default)]
+pub struct CampaignLoader;
+
+impl AssetLoader for CampaignLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), bevy::asset::Error>> {
+        Box::pin(async move {
+            let campaign = ron::Options::default()
+                .with_default_extension(ron::extensions::Extensions::IMPLICIT_SOME)
+                .from_bytes::<Campaign>(bytes)?;
+            load_context.set_default_asset(LoadedAsset::new(campaign));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["campaign.ron"]
+    }
+}
+
+// empty until the campaign is loaded, load_state starts it from the top
+#[derive(Default, Resource)]
+pub struct GameScriptState {
+    pub current: String,
+}
+
 impl GameScriptState {
-    pub fn next(&mut self) {
+    pub fn chapter<'a>(&self, campaign: &'a Campaign) -> Option::<&'a Chapter> {
+        campaign.chapter(&self.current)
+    }
+
+    // moves to the next chapter and returns where the game should go to play it
+    pub fn next(&mut self, campaign: &Campaign) -> AppState {
         println!("Moving from {:?}", self.current);
-        self.current = match self.current {
-            GameScript::IntroCutscene => GameScript::PreLevelOneCutscene,
-            GameScript::PreLevelOneCutscene => GameScript::LevelOneIntroCutscene,
-            GameScript::LevelOneIntroCutscene => GameScript::LevelOne,
-            GameScript::LevelOne => GameScript::LevelOnePostCutscene,
-            GameScript::LevelOnePostCutscene => GameScript::LevelTwoIntroCutscene,
-
-            GameScript::LevelTwoIntroCutscene => GameScript::LevelTwo,
-            GameScript::LevelTwo => GameScript::LevelTwoPostCutscene,
-            GameScript::LevelTwoPostCutscene => GameScript::LevelThreeIntroCutscene,
-
-            GameScript::LevelThreeIntroCutscene => GameScript::LevelThree,
-            GameScript::LevelThree => GameScript::LevelThreePostCutscene,
-            GameScript::LevelThreePostCutscene => GameScript::EndCutscene,
-
-            GameScript::LevelFourIntroCutscene => GameScript::LevelFour,
-            GameScript::LevelFour => GameScript::LevelFourPostCutscene,
-            GameScript::LevelFourPostCutscene => GameScript::LevelFiveIntroCutscene,
-
-            GameScript::LevelFiveIntroCutscene => GameScript::LevelFive,
-            GameScript::LevelFive => GameScript::LevelFivePostCutscene,
-            GameScript::LevelFivePostCutscene => GameScript::IntroCutscene, // todo: end?
-
-            _ => GameScript::IntroCutscene
+        let next = self.chapter(campaign).and_then(|c| c.next.clone());
+        let state = match next {
+            Some(next) => {
+                self.current = next;
+                AppState::LoadWorld
+            },
+            None => {
+                self.current = campaign.start.clone();
+                AppState::TitleScreen
+            }
         };
         println!("to {:?}", self.current);
+        state
     }
 }
 
@@ -86,29 +161,30 @@ fn load_state(
     mut assets_handler: asset_loading::AssetsHandler,
     mut game_assets: ResMut<GameAssets>,
     game_state: ResMut<game_state::GameState>,
-    game_script_state: Res<GameScriptState>,
+    mut game_script_state: ResMut<GameScriptState>,
+    campaigns: Res<Assets<Campaign>>,
 ) {
+    let campaign = campaigns.get(&game_assets.campaign).expect("Campaign not loaded");
+    if game_script_state.chapter(campaign).is_none() {
+        // either a new game or a save from a chapter that's since been removed
+        println!("No chapter {:?}, starting from the top", game_script_state.current);
+        game_script_state.current = campaign.start.clone();
+    }
+
     println!("Loading state {:?}", game_script_state.current);
-    match game_script_state.current {
-        GameScript::IntroCutscene 
-        | GameScript::EndCutscene
-        | GameScript::PreLevelOneCutscene
-        | GameScript::LevelOneIntroCutscene 
-        | GameScript::LevelOnePostCutscene 
-        | GameScript::LevelTwoIntroCutscene 
-        | GameScript::LevelTwoPostCutscene 
-        | GameScript::LevelThreeIntroCutscene 
-        | GameScript::LevelThreePostCutscene 
-        | GameScript::LevelFourIntroCutscene
-        | GameScript::LevelFourPostCutscene
-        | GameScript::LevelFiveIntroCutscene
-        | GameScript::LevelFivePostCutscene => assets_handler.load(AppState::Cutscene, &mut game_assets, &game_state),
-
-        GameScript::LevelOne
-        | GameScript::LevelTwo
-        | GameScript::LevelThree 
-        | GameScript::LevelFour
-        | GameScript::LevelFive => assets_handler.load(AppState::InGame, &mut game_assets, &game_state),
-        _ => ()
+    let chapter = game_script_state.chapter(campaign)
+                                   .expect("Chapter missing from campaign")
+                                   .clone();
+
+    assets_handler.add_glb(&mut game_assets.level, &chapter.level);
+    if let Some(bgm) = &chapter.bgm {
+        assets_handler.add_audio(&mut game_assets.chapter_bgm, bgm);
+    }
+
+    if let Some(cutscene) = &chapter.cutscene {
+        assets_handler.add_cutscene(&mut game_assets.cutscene, cutscene);
+        assets_handler.load(AppState::Cutscene, &mut game_assets, &game_state);
+    } else {
+        assets_handler.load(AppState::InGame, &mut game_assets, &game_state);
     }
 }
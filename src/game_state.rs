@@ -22,9 +22,30 @@ pub struct GameState {
     pub current_time: f32,
     pub graphics_high: bool,
     pub title_screen_cooldown: f32,
+    pub groups_broken: usize,
+    pub groups_repaired: usize,
+    // dollars of damage currently standing and what the whole shop is worth
+    pub damage: u32,
+    pub total_value: u32,
+    // what the score was before this level so retrying doesn't count it twice
+    pub round_start_score: f32,
+    pub round_start_score_check_count: usize,
 }
 
 impl GameState {
+    // puts everything back to how it was when the level started, the
+    // level's time limit gets set again when the level is spawned
+    pub fn reset_round(&mut self) {
+        self.score = self.round_start_score;
+        self.score_check_count = self.round_start_score_check_count;
+        self.live_score = 1.0;
+        self.level_ended = false;
+        self.level_end_cooldown = 0.0;
+        self.groups_broken = 0;
+        self.groups_repaired = 0;
+        self.damage = 0;
+    }
+
     pub fn initialize(graphics: bool, shadows_on: bool) -> Self {
         GameState {
             score: 1.0,
@@ -36,6 +57,12 @@ impl GameState {
             current_time: 120.0,
             graphics_high: graphics,
             title_screen_cooldown: 1.0,
+            groups_broken: 0,
+            groups_repaired: 0,
+            damage: 0,
+            total_value: 0,
+            round_start_score: 1.0,
+            round_start_score_check_count: 0,
         }
     }
 }
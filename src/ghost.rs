@@ -0,0 +1,209 @@
+use crate::{
+    assets::GameAssets, bull, game_script, game_state, ingame, menus::options, player, save, AppState,
+};
+use bevy::gltf::Gltf;
+use bevy::prelude::*;
+use bevy_scene_hook::{HookedSceneBundle, SceneHook};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+const GHOSTS_FILE: &str = "ghosts.ron";
+const SAMPLE_RATE: f32 = 0.1;
+const GHOST_ALPHA: f32 = 0.35;
+
+pub struct GhostPlugin;
+impl Plugin for GhostPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(save::read_file::<Ghosts>(GHOSTS_FILE).unwrap_or_default())
+            .init_resource::<GhostRecorder>()
+            .add_system_set(SystemSet::on_enter(AppState::InGame).with_system(setup))
+            .add_system_set(
+                SystemSet::on_update(AppState::InGame)
+                    .with_system(record_ghost.after(player::move_player))
+                    .with_system(move_ghosts.after(record_ghost))
+                    .with_system(animate_ghosts)
+            );
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct GhostSample {
+    pub time: f32,
+    pub translation: [f32; 3],
+    pub rotation: [f32; 4],
+}
+
+impl GhostSample {
+    fn new(time: f32, transform: &Transform) -> Self {
+        GhostSample {
+            time,
+            translation: transform.translation.to_array(),
+            rotation: transform.rotation.to_array(),
+        }
+    }
+
+    fn lerp(&self, other: &GhostSample, time: f32) -> Transform {
+        let t = if other.time > self.time { ((time - self.time) / (other.time - self.time)).clamp(0.0, 1.0) } else { 1.0 };
+        Transform {
+            translation: Vec3::from_array(self.translation).lerp(Vec3::from_array(other.translation), t),
+            rotation: Quat::from_array(self.rotation).slerp(Quat::from_array(other.rotation), t),
+            ..default()
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct GhostRun {
+    pub score: f32,
+    pub player: Vec::<GhostSample>,
+    pub bull: Vec::<GhostSample>,
+}
+
+// best run per level
+#[derive(Serialize, Deserialize, Default, Resource)]
+#[serde(default)]
+pub struct Ghosts {
+    pub runs: HashMap::<String, GhostRun>,
+}
+
+#[derive(Default, Resource)]
+struct GhostRecorder {
+    level: String,
+    elapsed: f32,
+    cooldown: f32,
+    run: GhostRun,
+    saved: bool,
+}
+
+#[derive(Component)]
+struct Ghost {
+    samples: Vec::<GhostSample>,
+}
+
+#[derive(Component)]
+struct GhostAnimation(Handle<AnimationClip>);
+
+fn setup(
+    mut commands: Commands,
+    mut recorder: ResMut<GhostRecorder>,
+    ghosts: Res<Ghosts>,
+    settings: Res<options::Settings>,
+    game_assets: Res<GameAssets>,
+    game_script_state: Res<game_script::GameScriptState>,
+    assets_gltf: Res<Assets<Gltf>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    *recorder = GhostRecorder {
+        level: game_script_state.current.clone(),
+        ..default()
+    };
+
+    if !settings.show_ghost {
+        return;
+    }
+    let best = match ghosts.runs.get(&game_script_state.current) {
+        Some(best) => best,
+        None => return,
+    };
+
+    let material = materials.add(StandardMaterial {
+        base_color: Color::rgba(0.8, 0.9, 1.0, GHOST_ALPHA),
+        alpha_mode: AlphaMode::Blend,
+        unlit: true,
+        ..default()
+    });
+
+    let ghosts = [
+        (&game_assets.matador, &game_assets.matador_run, &best.player),
+        (&game_assets.bull, &game_assets.bull_run, &best.bull),
+    ];
+    for (model, animation, samples) in ghosts {
+        let gltf = match assets_gltf.get(model) {
+            Some(gltf) if !samples.is_empty() => gltf,
+            _ => continue,
+        };
+
+        let material = material.clone();
+        let animation = animation.clone();
+        commands
+            .spawn(HookedSceneBundle {
+                scene: SceneBundle { scene: gltf.scenes[0].clone(), ..default() },
+                hook: SceneHook::new(move |entity, cmds, _| {
+                    if entity.contains::<Handle<StandardMaterial>>() {
+                        cmds.insert(material.clone())
+                            .insert(bevy::pbr::NotShadowCaster);
+                    }
+                    if entity.contains::<AnimationPlayer>() {
+                        cmds.insert(GhostAnimation(animation.clone()));
+                    }
+                }),
+            })
+            .insert(Ghost {
+                samples: samples.clone(),
+            })
+            .insert(ingame::CleanupMarker);
+    }
+}
+
+fn record_ghost(
+    mut recorder: ResMut<GhostRecorder>,
+    mut ghosts: ResMut<Ghosts>,
+    game_state: Res<game_state::GameState>,
+    players: Query<&Transform, With<player::Player>>,
+    bulls: Query<&Transform, With<bull::Bull>>,
+    time: Res<Time>,
+) {
+    if game_state.level_ended {
+        if !recorder.saved {
+            recorder.saved = true;
+            let best_score = ghosts.runs.get(&recorder.level).map(|run| run.score).unwrap_or(-1.0);
+            if game_state.live_score > best_score && !recorder.run.player.is_empty() {
+                let mut run = std::mem::take(&mut recorder.run);
+                run.score = game_state.live_score;
+                ghosts.runs.insert(recorder.level.clone(), run);
+                save::write_file(GHOSTS_FILE, &*ghosts);
+            }
+        }
+        return;
+    }
+
+    recorder.elapsed += time.delta_seconds();
+    recorder.cooldown -= time.delta_seconds();
+    if recorder.cooldown > 0.0 {
+        return;
+    }
+    recorder.cooldown = SAMPLE_RATE;
+
+    let elapsed = recorder.elapsed;
+    if let Some(transform) = players.iter().next() {
+        recorder.run.player.push(GhostSample::new(elapsed, transform));
+    }
+    if let Some(transform) = bulls.iter().next() {
+        recorder.run.bull.push(GhostSample::new(elapsed, transform));
+    }
+}
+
+fn move_ghosts(
+    recorder: Res<GhostRecorder>,
+    mut ghosts: Query<(&Ghost, &mut Transform)>,
+) {
+    let elapsed = recorder.elapsed;
+    for (ghost, mut transform) in &mut ghosts {
+        // first sample after now, the ghost sits at the end once it runs out
+        let next = ghost.samples.iter()
+                                .position(|sample| sample.time >= elapsed)
+                                .unwrap_or(ghost.samples.len() - 1);
+        let previous = next.saturating_sub(1);
+        *transform = ghost.samples[previous].lerp(&ghost.samples[next], elapsed);
+    }
+}
+
+fn animate_ghosts(
+    mut animations: Query<(&GhostAnimation, &mut AnimationPlayer), Added<GhostAnimation>>,
+) {
+    for (ghost_animation, mut animation) in &mut animations {
+        animation.play(ghost_animation.0.clone_weak()).repeat();
+        animation.resume();
+    }
+}
@@ -1,11 +1,25 @@
 use bevy::prelude::*;
-use crate::props;
+use crate::{props, score, AppState};
+use std::collections::{HashMap, HashSet};
+use std::f32::consts::PI;
+
+// what a group member is worth if neither the catalogue or the level says otherwise
+pub const DEFAULT_VALUE: u32 = 50;
+// how far something can slide or tilt before it counts as fully damaged
+const SLIDE_DISTANCE: f32 = 1.0;
+const TILT_ANGLE: f32 = PI / 4.0;
+// anything under this is just physics jitter
+pub const DAMAGED_THRESHOLD: f32 = 0.1;
 
 pub struct GroupPlugin;
 impl Plugin for GroupPlugin {
     fn build(&self, app: &mut App) {
         app.add_system(set_groups)
+           .init_resource::<GroupStatus>()
            .add_event::<RestoreGroupEvent>()
+           .add_system_set(SystemSet::on_enter(AppState::InGame).with_system(reset_group_status))
+           .add_system_set(SystemSet::on_enter(AppState::Cutscene).with_system(reset_group_status))
+           .add_system(track_group_status)
            .add_system(restore_group_handler);
     }
 }
@@ -13,32 +27,231 @@ impl Plugin for GroupPlugin {
 #[derive(Component)]
 pub struct GroupMarker(pub usize);
 
+// dollars, either from the props catalogue or a "Value" in the node's name
+#[derive(Component, Debug, Copy, Clone)]
+pub struct Value(pub u32);
+
 #[derive(Component, Debug)]
 pub struct GroupMember {
     pub group_id: usize,
+    pub value: u32,
     pub original_global_transform: Transform,
     pub original_transform: Transform
 }
 
+impl GroupMember {
+    // 0.0 - 1.0, the worst of how far it fell, slid or tilted
+    pub fn damage(&self, transform: &Transform, shattered: bool) -> f32 {
+        if shattered {
+            return 1.0;
+        }
+
+        let original = &self.original_transform;
+        let fall = (transform.translation.y - original.translation.y).abs() / score::BREAK_DISTANCE;
+        let slide = Vec2::new(transform.translation.x - original.translation.x,
+                              transform.translation.z - original.translation.z).length() / SLIDE_DISTANCE;
+        let tilt = transform.rotation.angle_between(original.rotation) / TILT_ANGLE;
+
+        fall.max(slide).max(tilt).clamp(0.0, 1.0)
+    }
+}
+
+pub struct GroupDamage {
+    pub value: u32,
+    pub damaged_value: f32,
+    // where the worst damaged member is
+    pub position: Vec3,
+    members: Vec::<Entity>,
+}
+
+impl GroupDamage {
+    // 0.0 - 1.0 weighted by what each member is worth
+    pub fn percent(&self) -> f32 {
+        if self.value == 0 {
+            0.0
+        } else {
+            self.damaged_value / self.value as f32
+        }
+    }
+
+    pub fn is_damaged(&self) -> bool {
+        self.percent() >= DAMAGED_THRESHOLD
+    }
+}
+
+struct MemberStatus {
+    group_id: usize,
+    value: u32,
+    damage: f32,
+    shattered: bool,
+    position: Vec3,
+}
+
+// kept up to date from whatever moved, broke or got repaired this frame
+// so nothing has to walk every group member to know how the shop is doing
+#[derive(Default, Resource)]
+pub struct GroupStatus {
+    members: HashMap::<Entity, MemberStatus>,
+    groups: HashMap::<usize, GroupDamage>,
+    damaged: HashSet::<usize>,
+    changed: Vec::<usize>,
+    pub total_value: u32,
+    pub damage: f32,
+}
+
+impl GroupStatus {
+    pub fn group(&self, group_id: usize) -> Option::<&GroupDamage> {
+        self.groups.get(&group_id)
+    }
+
+    pub fn damaged_groups(&self) -> impl Iterator<Item = (usize, &GroupDamage)> {
+        self.damaged.iter()
+                    .filter_map(|group_id| self.groups.get(group_id).map(|group| (*group_id, group)))
+    }
+
+    pub fn damaged_count(&self) -> usize {
+        self.damaged.len()
+    }
+
+    // groups whose damage was recalculated this frame
+    pub fn changed(&self) -> &[usize] {
+        &self.changed
+    }
+
+    fn refresh(&mut self, group_id: usize) {
+        let group = match self.groups.get_mut(&group_id) {
+            Some(group) => group,
+            None => return,
+        };
+        self.total_value -= group.value;
+        self.damage -= group.damaged_value;
+
+        let members = &self.members;
+        group.members.retain(|entity| members.contains_key(entity));
+        group.value = 0;
+        group.damaged_value = 0.0;
+        let mut worst = -1.0;
+        for member in group.members.iter().filter_map(|entity| members.get(entity)) {
+            group.value += member.value;
+            group.damaged_value += member.value as f32 * member.damage;
+            if member.damage > worst {
+                worst = member.damage;
+                group.position = member.position;
+            }
+        }
+
+        self.total_value += group.value;
+        self.damage = (self.damage + group.damaged_value).max(0.0);
+        if group.is_damaged() {
+            self.damaged.insert(group_id);
+        } else {
+            self.damaged.remove(&group_id);
+        }
+        if group.members.is_empty() {
+            self.groups.remove(&group_id);
+        }
+        self.changed.push(group_id);
+    }
+}
+
 pub struct RestoreGroupEvent {
     pub group_id: usize,
 }
 
 fn set_groups(
     mut commands: Commands,
-    markers: Query<(Entity, &Transform, &GlobalTransform, &GroupMarker)>,
+    markers: Query<(Entity, &Transform, &GlobalTransform, &GroupMarker, Option::<&Value>)>,
 ) {
-    for (entity, transform, global_transform, marker) in &markers {
+    for (entity, transform, global_transform, marker, value) in &markers {
         commands.entity(entity)
                 .remove::<GroupMarker>()
                 .insert(GroupMember {
                     group_id: marker.0,
+                    value: value.map(|v| v.0).unwrap_or(DEFAULT_VALUE),
                     original_global_transform: global_transform.compute_transform(), 
                     original_transform: transform.clone(),
                 });
     }
 }
 
+// the old scene's despawns happen during the state change so they never
+// show up as removals, everything gets dropped here instead
+fn reset_group_status(
+    mut status: ResMut<GroupStatus>,
+) {
+    *status = GroupStatus::default();
+}
+
+pub fn track_group_status(
+    mut status: ResMut<GroupStatus>,
+    members: Query<(Entity, &GroupMember, &Transform, &GlobalTransform), Or<(Changed<Transform>, Added<GroupMember>)>>,
+    mut break_event_reader: EventReader<props::BreakEvent>,
+    mut restore_group_event_reader: EventReader<RestoreGroupEvent>,
+    removed_members: RemovedComponents<GroupMember>,
+) {
+    status.changed.clear();
+    let mut dirty = HashSet::<usize>::new();
+
+    for entity in removed_members.iter() {
+        if let Some(member) = status.members.remove(&entity) {
+            dirty.insert(member.group_id);
+        }
+    }
+
+    // the broken scene is swapped in without moving the original so
+    // it has to be told it's broken rather than working it out
+    for event in break_event_reader.iter() {
+        if let Some(member) = status.members.get_mut(&event.entity) {
+            member.shattered = true;
+            member.damage = 1.0;
+            dirty.insert(member.group_id);
+        }
+    }
+
+    for event in restore_group_event_reader.iter() {
+        if let Some(group) = status.groups.get(&event.group_id) {
+            for entity in group.members.clone() {
+                if let Some(member) = status.members.get_mut(&entity) {
+                    member.shattered = false;
+                }
+            }
+            dirty.insert(event.group_id);
+        }
+    }
+
+    for (entity, group_member, transform, global_transform) in &members {
+        let status = &mut *status;
+        let member = status.members
+                           .entry(entity)
+                           .or_insert_with(|| MemberStatus {
+                               group_id: group_member.group_id,
+                               value: group_member.value,
+                               damage: 0.0,
+                               shattered: false,
+                               position: global_transform.translation(),
+                           });
+        member.damage = group_member.damage(transform, member.shattered);
+        member.position = global_transform.translation();
+
+        let group = status.groups
+                          .entry(group_member.group_id)
+                          .or_insert(GroupDamage {
+                              value: 0,
+                              damaged_value: 0.0,
+                              position: member.position,
+                              members: vec!(),
+                          });
+        if !group.members.contains(&entity) {
+            group.members.push(entity);
+        }
+        dirty.insert(group_member.group_id);
+    }
+
+    for group_id in dirty {
+        status.refresh(group_id);
+    }
+}
+
 fn restore_group_handler(
     mut commands: Commands,
     mut restore_group_event_handler: EventReader<RestoreGroupEvent>,
@@ -1,6 +1,7 @@
 use crate::{
     asset_loading, assets::GameAssets, cleanup, game_state, AppState, game_camera, player, bull, 
     DampPhysics, props::*, groups, shopkeeper, billboard, game_script, cutscene, dust, fishmonger,
+    audio::GameAudio, arcade, perception,
 };
 use bevy::prelude::*;
 use bevy::ecs::system::EntityCommands;
@@ -28,7 +29,7 @@ impl Plugin for InGamePlugin {
             .add_system_set(
                 SystemSet::on_exit(AppState::InGame).with_system(cleanup::<CleanupMarker>),
             )
-            .add_system_set(SystemSet::on_update(AppState::ResetInGame).with_system(reset_ingame))
+            .add_system_set(SystemSet::on_update(AppState::LevelOver).with_system(restart_level))
             .add_system(animate_fire)
             .add_plugin(HookPlugin)
             .add_plugin(CameraShakePlugin)
@@ -73,14 +74,15 @@ impl RandomSource for MyRandom {
     }
 }
 
-fn reset_ingame(
-    mut assets_handler: asset_loading::AssetsHandler,
-    mut game_assets: ResMut<GameAssets>,
+// InGame's cleanup has already run by the time we're here so the level
+// can be spawned again straight from the assets we already have
+fn restart_level(
+    mut state: ResMut<State<AppState>>,
     mut game_state: ResMut<game_state::GameState>,
-    mut game_script_state: ResMut<game_script::GameScriptState>,
 ) {
-    game_script_state.current = game_script::GameScript::LevelThreeIntroCutscene;
-    assets_handler.load(AppState::LoadWorld, &mut game_assets, &mut game_state);
+    println!("restarting level");
+    game_state.reset_round();
+    state.set(AppState::InGame).unwrap();
 }
 
 pub fn load(
@@ -124,14 +126,7 @@ pub fn load(
     assets_handler.add_material(&mut game_assets.pa_mouth, "textures/pa_mouth.png", true);
     assets_handler.add_material(&mut game_assets.pa_lookleft, "textures/pa_lookleft.png", true);
 
-    assets_handler.add_glb(&mut game_assets.intro_level, "models/intro.glb");
-    assets_handler.add_glb(&mut game_assets.outro_level, "models/outro.glb");
-    assets_handler.add_glb(&mut game_assets.pregame, "models/pregame.glb");
-    assets_handler.add_glb(&mut game_assets.level_one, "models/level_one.glb");
-    assets_handler.add_glb(&mut game_assets.level_two, "models/level_two.glb");
-    assets_handler.add_glb(&mut game_assets.level_three, "models/level_three.glb");
-//  assets_handler.add_glb(&mut game_assets.level_four, "models/level_four.glb");
-//  assets_handler.add_glb(&mut game_assets.level_five, "models/level_five.glb");
+    assets_handler.add_campaign(&mut game_assets.campaign, "main.campaign.ron");
 
     assets_handler.add_audio(&mut game_assets.mat_speak, "audio/mat_speak.wav");
     assets_handler.add_audio(&mut game_assets.pa_speak, "audio/pa_speak.wav");
@@ -163,34 +158,43 @@ pub fn setup(
     mut rapier: ResMut<RapierConfiguration>,
     mut clear_color: ResMut<ClearColor>,
     game_script_state: Res<game_script::GameScriptState>,
+    campaigns: Res<Assets<game_script::Campaign>>,
+    arcade: Res<arcade::Arcade>,
+    mut audio: GameAudio,
 ) {
     clear_color.0 = Color::hex("000000").unwrap(); 
     game_state.title_screen_cooldown = 1.0;
-    game_state.current_time = 90.0;
     game_state.live_score = 1.0;
-
-    let gltf = 
-        match game_script_state.current {
-            game_script::GameScript::IntroCutscene => assets_gltf.get(&game_assets.intro_level.clone()),
-            game_script::GameScript::EndCutscene => assets_gltf.get(&game_assets.outro_level.clone()),
-            game_script::GameScript::PreLevelOneCutscene => assets_gltf.get(&game_assets.pregame.clone()),
-            game_script::GameScript::LevelOneIntroCutscene 
-                | game_script::GameScript::LevelOnePostCutscene 
-                | game_script::GameScript::LevelOne => assets_gltf.get(&game_assets.level_one.clone()),
-            game_script::GameScript::LevelTwoIntroCutscene 
-                | game_script::GameScript::LevelTwoPostCutscene 
-                | game_script::GameScript::LevelTwo => assets_gltf.get(&game_assets.level_two.clone()),
-            game_script::GameScript::LevelThreeIntroCutscene 
-                | game_script::GameScript::LevelThreePostCutscene 
-                | game_script::GameScript::LevelThree => assets_gltf.get(&game_assets.level_three.clone()),
-            game_script::GameScript::LevelFourIntroCutscene 
-                | game_script::GameScript::LevelFourPostCutscene 
-                | game_script::GameScript::LevelFour => assets_gltf.get(&game_assets.level_four.clone()),
-            game_script::GameScript::LevelFiveIntroCutscene 
-                | game_script::GameScript::LevelFivePostCutscene 
-                | game_script::GameScript::LevelFive => assets_gltf.get(&game_assets.level_five.clone()),
-            _ => None  
+    game_state.groups_broken = 0;
+    game_state.groups_repaired = 0;
+    game_state.damage = 0;
+    game_state.total_value = 0;
+    game_state.round_start_score = game_state.score;
+    game_state.round_start_score_check_count = game_state.score_check_count;
+
+    let chapter = campaigns.get(&game_assets.campaign)
+                           .and_then(|campaign| game_script_state.chapter(campaign))
+                           .cloned();
+    let chapter = match chapter {
+        Some(chapter) => chapter,
+        None => {
+            println!("no chapter for {:?}", game_script_state.current);
+            return;
+        }
+    };
+    game_state.current_time =
+        if arcade.active {
+            arcade.time_limit(chapter.time_limit)
+        } else {
+            chapter.time_limit
         };
+    if chapter.bgm.is_some() {
+        audio.continue_bgm(&game_assets.chapter_bgm);
+    }
+
+    let gltf = assets_gltf.get(&game_assets.level);
+    let shadows_on = game_state.shadows_on;
+    let bull_kind = chapter.bull_kind;
 
     if let Some(gltf) = gltf {
         println!("got gltf");
@@ -220,7 +224,7 @@ pub fn setup(
                    }
 
                    if name.contains("PointLight") {
-                       handle_lights(cmds, name);
+                       handle_lights(cmds, name, shadows_on);
                    }
 
                    if name.contains("noshadowcast") {
@@ -240,7 +244,7 @@ pub fn setup(
                                 point_light: PointLight {
                                   intensity: 90.0, // lumens - roughly a 100W non-halogen incandescent bulb
                                   color: Color::rgba(255.0, 255.0, 255.0, 255.0),
-                                  shadows_enabled: true,
+                                  shadows_enabled: shadows_on,
                                     ..default()
                                 },
                                 ..default()
@@ -249,16 +253,18 @@ pub fn setup(
                    }
                    if name.contains("bull") {
                        let BULL_COLLISION_THRESHOLD: f32 = 0.40001;
+                       let kind = bull::BullKind::from_name(name).unwrap_or(bull_kind);
                        cmds.insert(NoFrustumCulling)
-                           .insert(Collider::cuboid(2.0, 2.0, 2.0))
-                           .insert(ColliderMassProperties::Density(5.0))
+                           .insert(Collider::cuboid(bull::COLLIDER_HALF_EXTENT, bull::COLLIDER_HALF_EXTENT, bull::COLLIDER_HALF_EXTENT))
+                           .insert(ColliderMassProperties::Density(kind.archetype().density))
                            .insert(Velocity::default())
                            .insert(LockedAxes::ROTATION_LOCKED_X | LockedAxes::ROTATION_LOCKED_Z | LockedAxes::ROTATION_LOCKED_Y) 
                            .insert(Ccd::enabled())
                            .insert(ActiveEvents::CONTACT_FORCE_EVENTS)
                            .insert(ContactForceEventThreshold(BULL_COLLISION_THRESHOLD))
                            .insert(RigidBody::Dynamic)
-                           .insert(bull::Bull::default());
+                           .insert(bull::Bull::new(kind))
+                           .insert(perception::Perception::default());
                    }
                    if name.contains("player") {
                        cmds.insert(player::PlayerBundle::new())
@@ -362,73 +368,72 @@ pub fn setup(
                    if name.contains("fishbowl") {
                        FishBowl::add_components(cmds, mesh);
                    }
+                   // after the props so the level can override the catalogue,
+                   // has to come before Group in the name, e.g. vaseValue300Group2
+                   if name.contains("Value") {
+                       let value = name.split("Value")
+                                       .last()
+                                       .expect("Value missing amount")
+                                       .chars()
+                                       .take_while(|c| c.is_ascii_digit())
+                                       .collect::<String>();
+                       let value = u32::from_str(&value).expect("Value not a number");
+                       cmds.insert(groups::Value(value));
+                   }
+                   // e.g. tablecollideShove200 slides twice as far as usual
+                   if name.contains("Shove") {
+                       let shove = name.split("Shove")
+                                       .last()
+                                       .expect("Shove missing amount")
+                                       .chars()
+                                       .take_while(|c| c.is_ascii_digit())
+                                       .collect::<String>();
+                       let shove = u32::from_str(&shove).expect("Shove not a number");
+                       cmds.insert(bull::Shove(shove as f32 / 100.0));
+                   }
+                   // e.g. shelfcollideSturdy, too heavy for a calf to move
+                   if name.contains("Sturdy") {
+                       cmds.insert(bull::Sturdy);
+                   }
 
                    cmds.insert(CleanupMarker);
                }
            }),
+        })
+        .insert(CleanupMarker);
+
+        commands.insert_resource(AmbientLight {
+            color: Color::WHITE,
+            brightness: chapter.ambient_light,
         });
 
-        match game_script_state.current {
-            game_script::GameScript::IntroCutscene => {
-                commands.insert_resource(AmbientLight {
-                    color: Color::WHITE,
-                    brightness: 0.00,
-                });
-            },
-            game_script::GameScript::PreLevelOneCutscene |
-            game_script::GameScript::EndCutscene 
-                => {
-                commands.insert_resource(AmbientLight {
-                    color: Color::WHITE,
-                    brightness: 0.50,
-                });
-            },
-            game_script::GameScript::LevelThree => {
-                commands.insert_resource(AmbientLight {
-                    color: Color::WHITE,
-                    brightness: 0.50,
-                });
-            },
-            game_script::GameScript::LevelTwo => {
-                commands.insert_resource(AmbientLight {
-                    color: Color::WHITE,
-                    brightness: 0.50,
-                });
-            },
-            _ => {
-                // lights
-                commands.insert_resource(AmbientLight {
-                    color: Color::WHITE,
-                    brightness: 0.50,
-                });
-
-                const HALF_SIZE: f32 = 100.0;
-                commands.spawn(DirectionalLightBundle {
-                    directional_light: DirectionalLight {
-                        illuminance: 50000.0,
-                        color: Color::rgba(1.0, 1.0, 1.0, 1.0),
-                        shadow_projection: OrthographicProjection {
-                            left: -HALF_SIZE,
-                            right: HALF_SIZE,
-                            bottom: -HALF_SIZE,
-                            top: HALF_SIZE,
-                            near: -10.0 * HALF_SIZE,
-                            far: 10.0 * HALF_SIZE,
-                            ..Default::default()
-                        },
-                        shadows_enabled: game_state.shadows_on,
+        if chapter.sun {
+            const HALF_SIZE: f32 = 100.0;
+            commands.spawn(DirectionalLightBundle {
+                directional_light: DirectionalLight {
+                    illuminance: 50000.0,
+                    color: Color::rgba(1.0, 1.0, 1.0, 1.0),
+                    shadow_projection: OrthographicProjection {
+                        left: -HALF_SIZE,
+                        right: HALF_SIZE,
+                        bottom: -HALF_SIZE,
+                        top: HALF_SIZE,
+                        near: -10.0 * HALF_SIZE,
+                        far: 10.0 * HALF_SIZE,
                         ..Default::default()
                     },
-                    transform: {
-                        let mut t = Transform::default();
-                        t.rotate_x(-1.6);
-                        t
-                    },        
+                    shadows_enabled: game_state.shadows_on,
                     ..Default::default()
-                })
-                .insert(cutscene::CutsceneCleanupMarker)
-                .insert(CleanupMarker);
-            }
+                },
+                transform: {
+                    let mut t = Transform::default();
+                    t.rotate_x(-1.6);
+                    t
+                },        
+                ..Default::default()
+            })
+            .insert(cutscene::CutsceneCleanupMarker)
+            .insert(CleanupMarker);
         }
 
         if camera.iter().len() == 0 {
@@ -496,13 +501,14 @@ pub fn setup(
 fn handle_lights(
     entity_commands: &mut EntityCommands,
     name: &str,
+    shadows_on: bool,
 ) {
     if name.contains("Fire") {
         entity_commands
             .insert(PointLight {
                 color: Color::rgb(0.78, 0.474, 0.0),
                 intensity: 90.0,
-                shadows_enabled: true,
+                shadows_enabled: shadows_on,
                 ..default()
             })
             .insert(FireLight {
@@ -519,7 +525,7 @@ fn handle_lights(
                 intensity: 100000.0,
                 range: 60.6,
                 radius: 12.2,
-                shadows_enabled: true,
+                shadows_enabled: shadows_on,
                 ..default()
             });
     }
@@ -530,7 +536,7 @@ fn handle_lights(
                 intensity: 2274.0,
                 range: 12.5,
                 radius: 0.0,
-                shadows_enabled: true,
+                shadows_enabled: shadows_on,
                 ..default()
             });
     }
@@ -541,7 +547,7 @@ fn handle_lights(
                 color: Color::rgb(0.00, 0.474, 0.78),
                 intensity: 20380.0,
                 range: 44.0,
-                shadows_enabled: true,
+                shadows_enabled: shadows_on,
                 ..default()
             })
         .insert(FishLight {
@@ -1,5 +1,5 @@
 use crate::{
-    assets::GameAssets, game_state, menus, AppState, ui::text_size, ingame
+    assets::GameAssets, game_state, menus, AppState, ui::text_size, ingame, score,
 };
 use bevy::prelude::*;
 
@@ -25,7 +25,6 @@ fn update_ui(
     mut destroyed_indicators: Query<&mut Text, (With<DestroyedIndicator>, Without<TimeIndicator>)>,
 ) {
 //    println!("Current score {}", game_state.live_score);
-    let current_score = (1.0 - game_state.live_score) * 100.0;
 //  for (mut image, star) in stars.iter_mut() {
 //      let star_value = star.0 as f32;
 //      if star_value + 0.5 < current_score {
@@ -38,7 +37,7 @@ fn update_ui(
 //  }
 
     for mut destroyed in &mut destroyed_indicators {
-        destroyed.sections[0].value = format!("{}%", current_score as usize);
+        destroyed.sections[0].value = format!("-{}", score::dollars(game_state.damage));
         destroyed.sections[0].style.color = Color::rgb(game_state.live_score, 1.0 - game_state.live_score, 0.0);
     }
     for mut text in &mut time_indicators {
@@ -92,14 +91,14 @@ fn setup(
                         parent,
                         game_assets.font.clone(),
                         text_scaler.scale(menus::DEFAULT_FONT_SIZE * 0.6),
-                        "Destroyed: ",
+                        "Damage: ",
                         Vec::<ingame::CleanupMarker>::new(), // just an empty vec since can't do <impl Trait>
                     );
                     add_title(
                         parent,
                         game_assets.font.clone(),
                         text_scaler.scale(menus::DEFAULT_FONT_SIZE * 0.6),
-                        "0%",
+                        "-$0",
                         vec!(DestroyedIndicator), // just an empty vec since can't do <impl Trait>
                     );
                 });
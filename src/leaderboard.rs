@@ -0,0 +1,370 @@
+use crate::{
+    arcade, assets::GameAssets, audio::GameAudio, cleanup, game_script, game_state, menus, save,
+    title_screen::MenuAction, ui::text_size, AppState,
+};
+use bevy::prelude::*;
+use leafwing_input_manager::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+const LEADERBOARDS_FILE: &str = "leaderboards.ron";
+const MAX_ENTRIES: usize = 10;
+const MODES: [Mode; 3] = [Mode::Story, Mode::Practice, Mode::Arcade];
+
+pub struct LeaderboardPlugin;
+impl Plugin for LeaderboardPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(save::read_file::<Leaderboards>(LEADERBOARDS_FILE).unwrap_or_default())
+            .init_resource::<LeaderboardView>()
+            // whoever was going to name the new entries had their chance
+            .add_system_set(SystemSet::on_exit(AppState::ScoreDisplay).with_system(forget_unnamed))
+            .add_system_set(SystemSet::on_exit(AppState::ArcadeOver).with_system(forget_unnamed))
+            .add_system_set(SystemSet::on_enter(AppState::Leaderboard).with_system(setup))
+            .add_system_set(
+                SystemSet::on_update(AppState::Leaderboard)
+                    .with_system(update_view)
+                    .with_system(update_text.after(update_view))
+            )
+            .add_system_set(
+                SystemSet::on_exit(AppState::Leaderboard).with_system(cleanup::<LeaderboardCleanupMarker>),
+            );
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Mode {
+    Story,
+    // played from the level select
+    Practice,
+    Arcade,
+}
+
+impl Mode {
+    pub fn current(arcade: &arcade::Arcade, save_slots: &save::SaveSlots) -> Self {
+        if arcade.active {
+            Mode::Arcade
+        } else if save_slots.active.is_some() {
+            Mode::Story
+        } else {
+            Mode::Practice
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            Mode::Story => "Story",
+            Mode::Practice => "Practice",
+            Mode::Arcade => "Arcade",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct LeaderboardEntry {
+    pub id: u64,
+    pub name: String,
+    pub date: String,
+    pub score: f32,
+    pub damage: u32,
+    pub time_remaining: f32,
+    pub groups_destroyed: usize,
+}
+
+impl LeaderboardEntry {
+    pub fn from_round(game_state: &game_state::GameState) -> Self {
+        LeaderboardEntry {
+            id: 0,
+            name: String::new(),
+            date: today(),
+            score: game_state.live_score.max(0.0),
+            damage: game_state.damage,
+            time_remaining: game_state.current_time.max(0.0),
+            groups_destroyed: game_state.groups_broken,
+        }
+    }
+
+    // higher score first, finishing with more time left breaks ties
+    fn beats(&self, other: &LeaderboardEntry) -> bool {
+        self.score > other.score || (self.score == other.score && self.time_remaining > other.time_remaining)
+    }
+}
+
+#[derive(Serialize, Deserialize, Resource)]
+#[serde(default)]
+pub struct Leaderboards {
+    pub tables: HashMap::<(String, Mode), Vec::<LeaderboardEntry>>,
+    // new entries go in under the last name used until they're given one
+    pub last_name: String,
+    next_id: u64,
+    #[serde(skip)]
+    unnamed: Vec::<u64>,
+}
+
+impl Default for Leaderboards {
+    fn default() -> Self {
+        Leaderboards {
+            tables: HashMap::new(),
+            last_name: "AAA".to_string(),
+            next_id: 0,
+            unnamed: vec!(),
+        }
+    }
+}
+
+impl Leaderboards {
+    pub fn table(&self, level: &str, mode: Mode) -> &[LeaderboardEntry] {
+        self.tables.get(&(level.to_string(), mode))
+                   .map(|table| table.as_slice())
+                   .unwrap_or(&[])
+    }
+
+    // returns where the entry placed, if it made the table at all
+    pub fn record(&mut self, level: &str, mode: Mode, mut entry: LeaderboardEntry) -> Option::<usize> {
+        entry.id = self.next_id;
+        entry.name = self.last_name.clone();
+        self.next_id += 1;
+
+        let table = self.tables.entry((level.to_string(), mode)).or_insert(vec!());
+        let position = table.iter()
+                            .position(|e| entry.beats(e))
+                            .unwrap_or(table.len());
+        if position >= MAX_ENTRIES {
+            return None;
+        }
+
+        self.unnamed.push(entry.id);
+        table.insert(position, entry);
+        table.truncate(MAX_ENTRIES);
+        save::write_file(LEADERBOARDS_FILE, self);
+        Some(position)
+    }
+
+    pub fn has_unnamed(&self) -> bool {
+        !self.unnamed.is_empty()
+    }
+
+    // puts a name on everything recorded since the last time someone entered one
+    pub fn name_unnamed(&mut self, name: &str) {
+        self.last_name = name.to_string();
+        let unnamed = std::mem::take(&mut self.unnamed);
+        for entry in self.tables.values_mut().flatten() {
+            if unnamed.contains(&entry.id) {
+                entry.name = name.to_string();
+            }
+        }
+        save::write_file(LEADERBOARDS_FILE, self);
+    }
+}
+
+fn forget_unnamed(
+    mut leaderboards: ResMut<Leaderboards>,
+) {
+    leaderboards.unnamed.clear();
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn today() -> String {
+    let seconds = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .map(|d| d.as_secs())
+                    .unwrap_or(0);
+
+    // days since 1970 to a calendar date, from Howard Hinnant's civil_from_days
+    let z = (seconds / 86400) as i64 + 719468;
+    let era = z.div_euclid(146097);
+    let day_of_era = z.rem_euclid(146097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    format!("{}-{:0>2}-{:0>2}", year, month, day)
+}
+
+// SystemTime isn't available in the browser
+#[cfg(target_arch = "wasm32")]
+fn today() -> String {
+    String::new()
+}
+
+#[derive(Component)]
+struct LeaderboardCleanupMarker;
+
+#[derive(Component)]
+struct TableTitle;
+
+#[derive(Component)]
+struct TableText;
+
+#[derive(Default, Resource)]
+struct LeaderboardView {
+    level: usize,
+    mode: usize,
+}
+
+fn setup(
+    mut commands: Commands,
+    game_assets: Res<GameAssets>,
+    mut view: ResMut<LeaderboardView>,
+    text_scaler: text_size::TextScaler,
+) {
+    *view = LeaderboardView::default();
+
+    commands
+        .spawn(InputManagerBundle {
+            input_map: MenuAction::default_input_map(),
+            action_state: ActionState::default(),
+        })
+        .insert(LeaderboardCleanupMarker);
+
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                position_type: PositionType::Absolute,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            background_color: Color::rgba(0.0, 0.0, 0.0, 0.5).into(),
+            ..Default::default()
+        })
+        .insert(LeaderboardCleanupMarker)
+        .with_children(|parent| {
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        size: Size::new(Val::Percent(70.0), Val::Percent(80.0)),
+                        justify_content: JustifyContent::Center,
+                        flex_direction: FlexDirection::ColumnReverse,
+                        align_items: AlignItems::Center,
+                        ..Default::default()
+                    },
+                    background_color: menus::MENU_BACKGROUND.into(),
+                    ..Default::default()
+                })
+                .with_children(|parent| {
+                    parent
+                        .spawn(TextBundle {
+                            text: Text::from_section(
+                                "",
+                                TextStyle {
+                                    font: game_assets.font.clone(),
+                                    font_size: text_scaler.scale(menus::DEFAULT_FONT_SIZE * 0.6),
+                                    color: Color::rgb(0.0, 0.0, 0.0),
+                                }
+                            ),
+                            ..Default::default()
+                        })
+                        .insert(TableTitle);
+                    parent
+                        .spawn(TextBundle {
+                            style: Style {
+                                margin: UiRect::all(Val::Percent(2.0)),
+                                ..Default::default()
+                            },
+                            text: Text::from_section(
+                                "",
+                                TextStyle {
+                                    font: game_assets.font.clone(),
+                                    font_size: text_scaler.scale(menus::BUTTON_LABEL_FONT_SIZE * 0.8),
+                                    color: Color::rgb(0.0, 0.0, 0.0),
+                                }
+                            ),
+                            ..Default::default()
+                        })
+                        .insert(TableText);
+                    parent.spawn(TextBundle {
+                        text: Text::from_section(
+                            "left/right: level  up/down: mode  select: back",
+                            TextStyle {
+                                font: game_assets.font.clone(),
+                                font_size: text_scaler.scale(menus::BUTTON_LABEL_FONT_SIZE * 0.6),
+                                color: Color::rgb(0.3, 0.3, 0.3),
+                            }
+                        ),
+                        ..Default::default()
+                    });
+                });
+        });
+}
+
+fn update_view(
+    mut view: ResMut<LeaderboardView>,
+    mut state: ResMut<State<AppState>>,
+    // the title screen's input entity is still around underneath
+    action_state: Query<&ActionState<MenuAction>, With<LeaderboardCleanupMarker>>,
+    game_assets: Res<GameAssets>,
+    campaigns: Res<Assets<game_script::Campaign>>,
+    mut game_state: ResMut<game_state::GameState>,
+    mut audio: GameAudio,
+) {
+    let action_state = match action_state.get_single() {
+        Ok(action_state) => action_state,
+        Err(_) => return,
+    };
+    let level_count = campaigns.get(&game_assets.campaign)
+                               .map(|campaign| campaign.levels().len())
+                               .unwrap_or(0)
+                               .max(1);
+
+    if action_state.just_pressed(MenuAction::Left) {
+        audio.play_sfx(&game_assets.blip);
+        view.level = view.level.checked_sub(1).unwrap_or(level_count - 1);
+    }
+    if action_state.just_pressed(MenuAction::Right) {
+        audio.play_sfx(&game_assets.blip);
+        view.level = (view.level + 1) % level_count;
+    }
+    if action_state.just_pressed(MenuAction::Up) {
+        audio.play_sfx(&game_assets.blip);
+        view.mode = view.mode.checked_sub(1).unwrap_or(MODES.len() - 1);
+    }
+    if action_state.just_pressed(MenuAction::Down) {
+        audio.play_sfx(&game_assets.blip);
+        view.mode = (view.mode + 1) % MODES.len();
+    }
+
+    if action_state.just_pressed(MenuAction::Select) {
+        audio.play_sfx(&game_assets.blip);
+        game_state.title_screen_cooldown = 0.3;
+        state.pop().unwrap();
+    }
+}
+
+fn update_text(
+    view: Res<LeaderboardView>,
+    leaderboards: Res<Leaderboards>,
+    game_assets: Res<GameAssets>,
+    campaigns: Res<Assets<game_script::Campaign>>,
+    mut titles: Query<&mut Text, (With<TableTitle>, Without<TableText>)>,
+    mut tables: Query<&mut Text, (With<TableText>, Without<TableTitle>)>,
+) {
+    let level = campaigns.get(&game_assets.campaign)
+                         .and_then(|campaign| campaign.levels().get(view.level).map(|level| level.id.clone()))
+                         .unwrap_or_default();
+    let mode = MODES[view.mode];
+
+    for mut text in &mut titles {
+        text.sections[0].value = format!("{} - {}", level.replace("_", " "), mode.label());
+    }
+
+    for mut text in &mut tables {
+        let table = leaderboards.table(&level, mode);
+        text.sections[0].value =
+            if table.is_empty() {
+                "No scores yet".to_string()
+            } else {
+                table.iter()
+                     .enumerate()
+                     .map(|(i, e)| format!("{:>2}. {} {:>4.0}%  {:0>2}:{:0>2} left  {} broken  {}",
+                                           i + 1, e.name, e.score * 100.0,
+                                           (e.time_remaining / 60.0) as usize, (e.time_remaining % 60.0) as usize,
+                                           e.groups_destroyed, e.date))
+                     .collect::<Vec::<_>>()
+                     .join("\n")
+            };
+    }
+}
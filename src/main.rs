@@ -1,24 +1,23 @@
 #![windows_subsystem = "windows"]
 use bevy::prelude::*;
-use bevy::app::AppExit;
 use bevy::diagnostic::{FrameTimeDiagnosticsPlugin, LogDiagnosticsPlugin};
 use bevy::window::WindowMode;
 use bevy_inspector_egui::{WorldInspectorPlugin, egui, bevy_egui};
 use bevy_rapier3d::prelude::*;
-use bevy_camera_shake::Shake3d;
-use bevy::gltf::Gltf;
-use bevy_scene_hook::{HookPlugin, SceneHook, HookedSceneBundle};
 use bevy_mod_outline::{
     AutoGenerateOutlineNormalsPlugin, OutlinePlugin, 
 };
 
-use bevy_flycam::{NoCameraPlayerPlugin, FlyCam};
+use bevy_flycam::NoCameraPlayerPlugin;
 
+mod arcade;
 mod asset_loading;
 mod assets;
 mod audio;
 mod bull;
+mod bull_mind;
 mod billboard;
+mod console;
 mod direction;
 mod dust;
 mod cutscene;
@@ -28,15 +27,23 @@ mod follow_text;
 mod fishmonger;
 mod game_state;
 mod game_script;
+mod ghost;
 mod groups;
 mod ingame;
 mod ingame_ui;
+mod leaderboard;
 mod menus;
+mod navmesh;
+mod perception;
 mod player;
+mod save;
 mod shopkeeper;
 mod splash;
+mod telegraph;
 mod score;
+mod score_display;
 mod props;
+mod replay;
 mod title_screen;
 mod ui;
 
@@ -55,6 +62,7 @@ fn main() {
         })
                      )
         .add_plugin(bull::BullPlugin)
+        .add_plugin(bull_mind::BullMindPlugin)
         .add_plugin(RapierPhysicsPlugin::<NoUserData>::default().with_physics_scale(10.0))
 //      .add_plugin(RapierDebugRenderPlugin::default())
 //      .add_plugin(LogDiagnosticsPlugin::default())
@@ -65,7 +73,9 @@ fn main() {
         .add_plugin(AutoGenerateOutlineNormalsPlugin)
         .add_plugin(billboard::BillboardPlugin)
         .add_plugin(dust::DustPlugin)
+        .add_plugin(arcade::ArcadePlugin)
         .add_plugin(audio::GameAudioPlugin)
+        .add_plugin(console::ConsolePlugin)
         .add_plugin(cutscene::CutscenePlugin)
         .add_plugin(asset_loading::AssetLoadingPlugin)
         .add_plugin(assets::AssetsPlugin)
@@ -73,20 +83,28 @@ fn main() {
         .add_plugin(follow_text::FollowTextPlugin)
         .add_plugin(game_state::GameStatePlugin)
         .add_plugin(game_script::GameScriptPlugin)
+        .add_plugin(ghost::GhostPlugin)
         .add_plugin(groups::GroupPlugin)
         .add_plugin(ingame::InGamePlugin)
         .add_plugin(ingame_ui::InGameUIPlugin)
+        .add_plugin(leaderboard::LeaderboardPlugin)
+        .add_plugin(menus::options::OptionsPlugin)
+        .add_plugin(menus::pause::PausePlugin)
+        .add_plugin(navmesh::NavMeshPlugin)
+        .add_plugin(perception::PerceptionPlugin)
         .add_plugin(player::PlayerPlugin)
         .add_plugin(props::PropsPlugin)
+        .add_plugin(replay::ReplayPlugin)
+        .add_plugin(save::SavePlugin)
         .add_plugin(score::ScorePlugin)
+        .add_plugin(score_display::ScoreDisplayPlugin)
         .add_plugin(splash::SplashPlugin)
+        .add_plugin(telegraph::TelegraphPlugin)
         .add_plugin(fishmonger::FishMongerPlugin)
         .add_plugin(shopkeeper::ShopKeeperPlugin)
         .add_plugin(title_screen::TitlePlugin)
         .add_plugin(ui::text_size::TextSizePlugin)
 
-//      .add_system(debug)
-//      .add_system(debug_2)
 //        .add_system(initial_damp_physics)
         .add_startup_system(window_settings)
         .add_state(AppState::Initial)
@@ -103,11 +121,13 @@ pub enum AppState {
     Debug,
     TitleScreen,
     Options,
+    Leaderboard,
     LoadWorld,
     InGame,
     Splash,
     ScoreDisplay,
     LevelOver,
+    ArcadeOver,
     ResetInGame,
     Loading,
 }
@@ -123,9 +143,10 @@ fn bootstrap(
     mut game_assets: ResMut<assets::GameAssets>,
     game_state: ResMut<game_state::GameState>,
     mut clear_color: ResMut<ClearColor>,
-    mut audio: audio::GameAudio,
+    mut settings: ResMut<menus::options::Settings>,
 ) {
-    audio.set_volume();
+    // applied by the options plugin once it sees the change
+    *settings = save::read_file(menus::options::SETTINGS_FILE).unwrap_or_default();
     clear_color.0 = Color::hex("aaaaaa").unwrap();
 
     assets_handler.load(AppState::Splash, &mut game_assets, &game_state);
@@ -151,158 +172,6 @@ impl ZeroSignum for Vec3 {
     }
 }
 
-const TRAUMA_AMOUNT: f32 = 0.5;
-fn debug(
-    mut commands: Commands,
-    keys: Res<Input<KeyCode>>,
-    mut game_state: ResMut<game_state::GameState>,
-    mut exit: ResMut<Events<AppExit>>,
-    mut assets_handler: asset_loading::AssetsHandler,
-    mut game_assets: ResMut<assets::GameAssets>,
-    mut shakeables: Query<&mut Shake3d>,
-    mut rapier: ResMut<RapierConfiguration>,
-    mut restore_group_event_writer: EventWriter<groups::RestoreGroupEvent>,
-    cameras: Query<(Entity, &Transform, &game_camera::PanOrbitCamera), With<Camera3d>>,
-    plates: Query<(Entity, &Parent, &Velocity), With<props::Plate>>,
-    parent_transforms: Query<&Transform>,
-    assets_gltf: Res<Assets<Gltf>>,
-    mut dust_spawn_event_writer: EventWriter<dust::DustSpawnEvent>,
-    mut cutscene_state: ResMut<cutscene::CutsceneState>,
-//   mut velocities: Query<(Entity, &mut Velocity), Without<bull::Bull>>,
-) {
-    if keys.just_pressed(KeyCode::Q) {
-        exit.send(AppExit);
-    }
-
-    if keys.just_pressed(KeyCode::R) {
-        assets_handler.load(AppState::ResetInGame, &mut game_assets, &game_state);
-    }
-
-
-    if keys.just_pressed(KeyCode::E) {
-        for mut shakeable in shakeables.iter_mut() {
-            shakeable.trauma = f32::min(shakeable.trauma + TRAUMA_AMOUNT, 1.0);
-        }
-    }
-
-    if keys.just_pressed(KeyCode::P) {
-        rapier.physics_pipeline_active = !rapier.physics_pipeline_active;
-        rapier.query_pipeline_active = !rapier.query_pipeline_active;
-
-        rapier.timestep_mode =  TimestepMode::Variable {
-            time_scale: 1.0,
-            max_dt: 1.0,
-            substeps: 1,
-        };
-//          timestep_mode: TimestepMode::Variable {
-//              max_dt: 1.0 / 60.0,
-//              time_scale: 1.0,
-//              substeps: 1,
-//          },
-    }
-
-//  for (e,v) in &mut velocities {
-//      if v.linvel.length() > 0.0 {
-//          println!("{:?} V: {:?}", e, v);
-//      }
-//  }
-
-    if keys.just_pressed(KeyCode::O) {
-        println!("Pressed O");
-
-        for (entity, parent, velocity) in &plates {
-            println!("replacing a thing");
-            let transform = parent_transforms.get(parent.get()).unwrap().clone();
-            let velocity = velocity.clone();
-            commands.get_or_spawn(entity).despawn_recursive();
-
-            if let Some(gltf) = assets_gltf.get(&game_assets.broken_plate.clone()) {
-                commands.spawn(HookedSceneBundle {
-                    scene: SceneBundle { scene: gltf.scenes[0].clone(), ..default() },
-                    hook: SceneHook::new(move |entity, cmds, mesh| {
-                        if let Some(name) = entity.get::<Name>().map(|t|t.as_str()) {
-                            if name.contains("plate") {
-                                use props::ComponentAdder;
-                                props::BrokenPlate::add_components(cmds, mesh); 
-                                cmds.insert(velocity.clone());
-                                cmds.insert(transform.clone());
-                            }
-                        }
-                    })
-                });
-            }
-        }
-    }
-
-    if keys.just_pressed(KeyCode::G) {
-        println!("sending group event");
-        restore_group_event_writer.send(groups::RestoreGroupEvent {
-            group_id: 1
-        });
-    }
-
-    if keys.just_pressed(KeyCode::U) {
-        for (_, transform, pan) in &cameras {
-            println!("C: {:?} {:?} {:?}", transform.translation, transform.rotation.to_axis_angle(), pan.focus);
-            println!("Forward: {:?}", transform.forward());
-        }
-    }
-
-    if keys.just_pressed(KeyCode::C) {
-        dust_spawn_event_writer.send(dust::DustSpawnEvent {
-            position: Vec3::new(0.0, 0.5, 0.0),
-            count: 10,
-            spread: 6.0,
-            rate: 0.5,
-            dust_time_to_live: 3.0,
-            emitter_time_to_live: 0.0,
-            size: 2.0,
-            ..default()
-        });
-    }
-
-    if keys.just_pressed(KeyCode::V) {
-        // skip cutscene
-        cutscene_state.cutscene_index = 99999;
-        cutscene_state.waiting_on_input = false;
-        game_state.current_time = 0.0;
-    }
-
-    if keys.just_pressed(KeyCode::F) {
-        for (entity, _, _) in &cameras {
-            commands.entity(entity).insert(FlyCam);
-        }
-    }
-}
-
-fn debug_2(
-    keys: Res<Input<KeyCode>>,
-    mut follow_text_event_writer: EventWriter<follow_text::FollowTextEvent>,
-    mut chase_event_writer: EventWriter<fishmonger::ChaseEvent>,
-    mut hit_player_event_writer: EventWriter<player::HitPlayerEvent>,
-    players: Query<Entity, With<player::Player>>,
-) {
-    if keys.just_pressed(KeyCode::H) {
-        for e in &players {
-            follow_text_event_writer.send(follow_text::FollowTextEvent {
-                follow: follow_text::FollowThing::Entity(e),
-                text: "oh geez!".to_string(),
-                color: Color::WHITE,
-                time_to_live: 6.0,
-            });
-        }
-    }
-
-
-    if keys.just_pressed(KeyCode::T) {
-        hit_player_event_writer.send(player::HitPlayerEvent);
-    }
-
-    if keys.just_pressed(KeyCode::Y) {
-        chase_event_writer.send(fishmonger::ChaseEvent);
-    }
-}
-
 fn window_settings(mut windows: ResMut<Windows>) {
     for window in windows.iter_mut() {
         window.set_title(String::from(""));
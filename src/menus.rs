@@ -2,10 +2,50 @@ use bevy::prelude::*;
 
 pub const NORMAL_BUTTON: Color = Color::rgba(1.00, 1.00, 1.00, 0.0);
 pub const HOVERED_BUTTON: Color = Color::rgb(0.552, 0.6, 0.682);
+pub const MENU_BACKGROUND: Color = Color::rgba(1.00, 1.00, 0.98, 0.9);
 pub const DEFAULT_FONT_SIZE: f32 = 90.0;
 pub const BY_LINE_FONT_SIZE: f32 = 45.0;
 pub const FOLLOW_FONT_SIZE: f32 = 40.0;
 pub const BUTTON_LABEL_FONT_SIZE: f32 = 40.0;
 pub const SCORE_FONT_SIZE: f32 = 84.0;
 
-//pub mod options;
+pub mod pause;
+pub mod options;
+pub mod name_entry;
+
+pub fn spawn_button(
+    builder: &mut ChildBuilder<'_, '_, '_>,
+    font: Handle<Font>,
+    font_size: f32,
+    height: f32,
+    label: &str,
+    component: impl Component,
+) {
+    builder
+        .spawn(ButtonBundle {
+            style: Style {
+                position_type: PositionType::Relative,
+                margin: UiRect::all(Val::Auto),
+                size: Size::new(Val::Percent(100.0), Val::Percent(height)),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            background_color: NORMAL_BUTTON.into(),
+            ..Default::default()
+        })
+        .with_children(|parent| {
+            parent.spawn(TextBundle {
+                text: Text::from_section(
+                    label,
+                    TextStyle {
+                        font,
+                        font_size,
+                        color: Color::rgb(0.0, 0.0, 0.0),
+                    }
+                ),
+                ..Default::default()
+            });
+        })
+        .insert(component);
+}
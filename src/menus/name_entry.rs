@@ -0,0 +1,87 @@
+use crate::title_screen::MenuAction;
+use bevy::prelude::*;
+use leafwing_input_manager::prelude::*;
+
+pub const NAME_LENGTH: usize = 3;
+
+#[derive(PartialEq)]
+pub enum NameInput {
+    Nothing,
+    Changed,
+    Confirmed,
+}
+
+// arcade style three letter initials, up/down picks the letter and
+// left/right moves between them
+#[derive(Resource)]
+pub struct NameEntry {
+    letters: [u8; NAME_LENGTH],
+    cursor: usize,
+    pub entering: bool,
+}
+
+impl Default for NameEntry {
+    fn default() -> Self {
+        NameEntry {
+            letters: [b'A'; NAME_LENGTH],
+            cursor: 0,
+            entering: false,
+        }
+    }
+}
+
+impl NameEntry {
+    // starts from whatever name was used last
+    pub fn new(name: &str, entering: bool) -> Self {
+        let mut letters = [b'A'; NAME_LENGTH];
+        for (letter, c) in letters.iter_mut().zip(name.bytes().filter(|c| c.is_ascii_uppercase())) {
+            *letter = c;
+        }
+
+        NameEntry {
+            letters,
+            cursor: 0,
+            entering,
+        }
+    }
+
+    pub fn name(&self) -> String {
+        self.letters.iter().map(|c| *c as char).collect()
+    }
+
+    pub fn handle_input(&mut self, action_state: &ActionState<MenuAction>) -> NameInput {
+        let cursor = self.cursor;
+        if action_state.just_pressed(MenuAction::Select) {
+            self.entering = false;
+            return NameInput::Confirmed;
+        }
+
+        if action_state.just_pressed(MenuAction::Up) {
+            self.letters[cursor] = if self.letters[cursor] == b'Z' { b'A' } else { self.letters[cursor] + 1 };
+        } else if action_state.just_pressed(MenuAction::Down) {
+            self.letters[cursor] = if self.letters[cursor] == b'A' { b'Z' } else { self.letters[cursor] - 1 };
+        } else if action_state.just_pressed(MenuAction::Left) {
+            self.cursor = cursor.checked_sub(1).unwrap_or(NAME_LENGTH - 1);
+        } else if action_state.just_pressed(MenuAction::Right) {
+            self.cursor = (cursor + 1) % NAME_LENGTH;
+        } else {
+            return NameInput::Nothing;
+        }
+
+        NameInput::Changed
+    }
+
+    // the letters with brackets around the one being changed
+    pub fn display(&self) -> String {
+        self.letters.iter()
+                    .enumerate()
+                    .map(|(i, c)| {
+                        if i == self.cursor {
+                            format!("[{}]", *c as char)
+                        } else {
+                            format!(" {} ", *c as char)
+                        }
+                    })
+                    .collect::<String>()
+    }
+}
@@ -0,0 +1,388 @@
+use crate::{
+    assets::GameAssets, audio::GameAudio, cleanup, game_state, menus, save,
+    title_screen::MenuAction, ui::text_size, AppState,
+};
+use bevy::prelude::*;
+use bevy::window::WindowMode;
+use leafwing_input_manager::prelude::*;
+use serde::{Deserialize, Serialize};
+
+pub const SETTINGS_FILE: &str = "settings.ron";
+const VOLUME_STEP: f32 = 0.1;
+
+pub struct OptionsPlugin;
+impl Plugin for OptionsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Settings>()
+            .add_system(apply_settings)
+            .add_system_set(SystemSet::on_enter(AppState::Options).with_system(setup))
+            .add_system_set(
+                SystemSet::on_update(AppState::Options)
+                    .with_system(update_menu_buttons)
+                    .with_system(update_labels.after(update_menu_buttons))
+            )
+            .add_system_set(
+                SystemSet::on_exit(AppState::Options)
+                    .with_system(save_settings)
+                    .with_system(cleanup::<OptionsCleanupMarker>)
+            );
+    }
+}
+
+#[derive(Serialize, Deserialize, Resource, Clone)]
+#[serde(default)]
+pub struct Settings {
+    pub music_volume: f32,
+    pub sound_volume: f32,
+    pub talk_volume: f32,
+    pub shadows_on: bool,
+    pub graphics_high: bool,
+    pub window_mode: WindowSetting,
+    pub text_speed: TextSpeed,
+    pub show_ghost: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            music_volume: 1.0,
+            sound_volume: 1.0,
+            talk_volume: 1.0,
+            shadows_on: true,
+            graphics_high: true,
+            window_mode: WindowSetting::Windowed,
+            text_speed: TextSpeed::Normal,
+            show_ghost: true,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum WindowSetting {
+    Windowed,
+    Fullscreen,
+    Borderless,
+}
+
+impl WindowSetting {
+    fn next(self) -> Self {
+        match self {
+            WindowSetting::Windowed => WindowSetting::Fullscreen,
+            WindowSetting::Fullscreen => WindowSetting::Borderless,
+            WindowSetting::Borderless => WindowSetting::Windowed,
+        }
+    }
+
+    fn previous(self) -> Self {
+        self.next().next()
+    }
+
+    fn window_mode(self) -> WindowMode {
+        match self {
+            WindowSetting::Windowed => WindowMode::Windowed,
+            WindowSetting::Fullscreen => WindowMode::Fullscreen,
+            WindowSetting::Borderless => WindowMode::BorderlessFullscreen,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum TextSpeed {
+    Slow,
+    Normal,
+    Fast,
+}
+
+impl TextSpeed {
+    fn next(self) -> Self {
+        match self {
+            TextSpeed::Slow => TextSpeed::Normal,
+            TextSpeed::Normal => TextSpeed::Fast,
+            TextSpeed::Fast => TextSpeed::Slow,
+        }
+    }
+
+    fn previous(self) -> Self {
+        self.next().next()
+    }
+
+    // scales the delay between words in the cutscene text box
+    pub fn delay_multiplier(self) -> f32 {
+        match self {
+            TextSpeed::Slow => 1.5,
+            TextSpeed::Normal => 1.0,
+            TextSpeed::Fast => 0.5,
+        }
+    }
+}
+
+#[derive(Component)]
+struct OptionsCleanupMarker;
+
+#[derive(Component, Copy, Clone, PartialEq)]
+enum OptionsButton {
+    Music,
+    Sound,
+    Talk,
+    Shadows,
+    Graphics,
+    Window,
+    TextSpeed,
+    Ghost,
+    Back,
+}
+
+impl OptionsButton {
+    fn label(&self, settings: &Settings) -> String {
+        let on_off = |on: bool| if on { "On" } else { "Off" };
+        match self {
+            OptionsButton::Music => format!("Music: {:.0}%", settings.music_volume * 100.0),
+            OptionsButton::Sound => format!("Sound: {:.0}%", settings.sound_volume * 100.0),
+            OptionsButton::Talk => format!("Voices: {:.0}%", settings.talk_volume * 100.0),
+            OptionsButton::Shadows => format!("Shadows: {}", on_off(settings.shadows_on)),
+            OptionsButton::Graphics => format!("Graphics: {}", if settings.graphics_high { "High" } else { "Low" }),
+            OptionsButton::Window => format!("Window: {}", match settings.window_mode {
+                                                WindowSetting::Windowed => "Windowed",
+                                                WindowSetting::Fullscreen => "Fullscreen",
+                                                WindowSetting::Borderless => "Borderless",
+                                            }),
+            OptionsButton::TextSpeed => format!("Text Speed: {}", match settings.text_speed {
+                                                TextSpeed::Slow => "Slow",
+                                                TextSpeed::Normal => "Normal",
+                                                TextSpeed::Fast => "Fast",
+                                            }),
+            OptionsButton::Ghost => format!("Best Run Ghost: {}", on_off(settings.show_ghost)),
+            OptionsButton::Back => "Back".to_string(),
+        }
+    }
+
+    // direction is -1 for left and 1 for right, select counts as right
+    fn change(&self, settings: &mut Settings, direction: f32) {
+        let step_volume = |volume: f32| ((volume + VOLUME_STEP * direction) * 10.0).round() / 10.0;
+        match self {
+            OptionsButton::Music => settings.music_volume = step_volume(settings.music_volume).clamp(0.0, 1.0),
+            OptionsButton::Sound => settings.sound_volume = step_volume(settings.sound_volume).clamp(0.0, 1.0),
+            OptionsButton::Talk => settings.talk_volume = step_volume(settings.talk_volume).clamp(0.0, 1.0),
+            OptionsButton::Shadows => settings.shadows_on = !settings.shadows_on,
+            OptionsButton::Graphics => settings.graphics_high = !settings.graphics_high,
+            OptionsButton::Window => {
+                settings.window_mode = if direction > 0.0 { settings.window_mode.next() } else { settings.window_mode.previous() };
+            },
+            OptionsButton::TextSpeed => {
+                settings.text_speed = if direction > 0.0 { settings.text_speed.next() } else { settings.text_speed.previous() };
+            },
+            OptionsButton::Ghost => settings.show_ghost = !settings.show_ghost,
+            OptionsButton::Back => (),
+        }
+    }
+}
+
+// runs whenever the settings change, including when bootstrap loads them
+fn apply_settings(
+    settings: Res<Settings>,
+    mut audio: GameAudio,
+    mut game_state: ResMut<game_state::GameState>,
+    mut windows: ResMut<Windows>,
+    mut msaa: ResMut<Msaa>,
+    mut directional_lights: Query<&mut DirectionalLight>,
+    mut point_lights: Query<&mut PointLight>,
+) {
+    if !settings.is_changed() {
+        return;
+    }
+
+    audio.set_volume(settings.music_volume, settings.sound_volume, settings.talk_volume);
+
+    game_state.shadows_on = settings.shadows_on;
+    game_state.graphics_high = settings.graphics_high;
+    for mut light in &mut directional_lights {
+        light.shadows_enabled = settings.shadows_on;
+    }
+    for mut light in &mut point_lights {
+        light.shadows_enabled = settings.shadows_on;
+    }
+
+    let samples = if settings.graphics_high { 4 } else { 1 };
+    if msaa.samples != samples {
+        msaa.samples = samples;
+    }
+
+    if let Some(window) = windows.get_primary_mut() {
+        if window.mode() != settings.window_mode.window_mode() {
+            window.set_mode(settings.window_mode.window_mode());
+        }
+    }
+}
+
+fn save_settings(
+    settings: Res<Settings>,
+) {
+    save::write_file(SETTINGS_FILE, &*settings);
+}
+
+fn setup(
+    mut commands: Commands,
+    game_assets: Res<GameAssets>,
+    settings: Res<Settings>,
+    text_scaler: text_size::TextScaler,
+) {
+    commands
+        .spawn(InputManagerBundle {
+            input_map: MenuAction::default_input_map(),
+            action_state: ActionState::default(),
+        })
+        .insert(OptionsCleanupMarker);
+
+    let buttons = [
+        OptionsButton::Music,
+        OptionsButton::Sound,
+        OptionsButton::Talk,
+        OptionsButton::Shadows,
+        OptionsButton::Graphics,
+        OptionsButton::Window,
+        OptionsButton::TextSpeed,
+        OptionsButton::Ghost,
+        OptionsButton::Back,
+    ];
+
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                position_type: PositionType::Absolute,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            background_color: Color::rgba(0.0, 0.0, 0.0, 0.5).into(),
+            ..Default::default()
+        })
+        .insert(OptionsCleanupMarker)
+        .with_children(|parent| {
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        size: Size::new(Val::Percent(40.0), Val::Percent(70.0)),
+                        justify_content: JustifyContent::Center,
+                        flex_direction: FlexDirection::ColumnReverse,
+                        align_items: AlignItems::Center,
+                        ..Default::default()
+                    },
+                    background_color: menus::MENU_BACKGROUND.into(),
+                    ..Default::default()
+                })
+                .with_children(|parent| {
+                    parent.spawn(TextBundle {
+                        text: Text::from_section(
+                            "Options",
+                            TextStyle {
+                                font: game_assets.font.clone(),
+                                font_size: text_scaler.scale(menus::DEFAULT_FONT_SIZE * 0.6),
+                                color: Color::rgb(0.0, 0.0, 0.0),
+                            }
+                        ),
+                        ..Default::default()
+                    });
+
+                    for button in buttons {
+                        menus::spawn_button(
+                            parent,
+                            game_assets.font.clone(),
+                            text_scaler.scale(menus::BUTTON_LABEL_FONT_SIZE),
+                            10.0,
+                            &button.label(&settings),
+                            button,
+                        );
+                    }
+                });
+        });
+}
+
+fn update_labels(
+    settings: Res<Settings>,
+    buttons: Query<(&OptionsButton, &Children)>,
+    mut texts: Query<&mut Text>,
+) {
+    if !settings.is_changed() {
+        return;
+    }
+
+    for (button, children) in &buttons {
+        for child in children.iter() {
+            if let Ok(mut text) = texts.get_mut(*child) {
+                text.sections[0].value = button.label(&settings);
+            }
+        }
+    }
+}
+
+fn update_menu_buttons(
+    mut selected_button: Local<usize>,
+    mut state: ResMut<State<AppState>>,
+    mut settings: ResMut<Settings>,
+    buttons: Query<&OptionsButton, With<Button>>,
+    // the menu underneath has buttons of its own
+    mut button_colors: Query<&mut BackgroundColor, (With<Button>, With<OptionsButton>)>,
+    // whatever menu pushed us still has its own input entity around
+    action_state: Query<&ActionState<MenuAction>, With<OptionsCleanupMarker>>,
+    mut game_state: ResMut<game_state::GameState>,
+    mut audio: GameAudio,
+    game_assets: Res<GameAssets>,
+) {
+    let action_state = match action_state.get_single() {
+        Ok(action_state) => action_state,
+        Err(_) => return,
+    };
+    let number_of_buttons = buttons.iter().count();
+    if number_of_buttons == 0 {
+        return;
+    }
+
+    if action_state.just_pressed(MenuAction::Up) {
+        audio.play_sfx(&game_assets.blip);
+        *selected_button = selected_button
+            .checked_sub(1)
+            .unwrap_or(number_of_buttons - 1);
+    }
+    if action_state.just_pressed(MenuAction::Down) {
+        audio.play_sfx(&game_assets.blip);
+        *selected_button = (*selected_button + 1) % number_of_buttons;
+    }
+
+    for (i, mut color) in button_colors.iter_mut().enumerate() {
+        if i == *selected_button {
+            *color = menus::HOVERED_BUTTON.into();
+        } else {
+            *color = menus::NORMAL_BUTTON.into();
+        }
+    }
+
+    let button = match buttons.iter().nth(*selected_button) {
+        Some(button) => *button,
+        None => return,
+    };
+
+    if button == OptionsButton::Back {
+        if action_state.just_pressed(MenuAction::Select) {
+            audio.play_sfx(&game_assets.blip);
+            *selected_button = 0;
+            game_state.title_screen_cooldown = 0.3;
+            state.pop().unwrap();
+        }
+        return;
+    }
+
+    let direction =
+        if action_state.just_pressed(MenuAction::Left) {
+            -1.0
+        } else if action_state.just_pressed(MenuAction::Right) || action_state.just_pressed(MenuAction::Select) {
+            1.0
+        } else {
+            0.0
+        };
+
+    if direction != 0.0 {
+        button.change(&mut settings, direction);
+        audio.play_sfx(&game_assets.blip);
+    }
+}
@@ -0,0 +1,244 @@
+use crate::{
+    asset_loading, assets::GameAssets, audio::GameAudio, cleanup, game_state, menus, player,
+    title_screen::MenuAction, ui::text_size, AppState,
+};
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+use leafwing_input_manager::prelude::*;
+
+pub struct PausePlugin;
+impl Plugin for PausePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PauseState>()
+            .add_system_set(
+                SystemSet::on_update(AppState::InGame)
+                    .with_system(pause_game)
+                    .with_system(handle_pause_exit)
+            )
+            .add_system_set(SystemSet::on_enter(AppState::Pause).with_system(setup))
+            .add_system_set(
+                SystemSet::on_update(AppState::Pause)
+                    .with_system(update_menu_buttons)
+            )
+            .add_system_set(
+                SystemSet::on_exit(AppState::Pause)
+                    .with_system(unfreeze)
+                    .with_system(cleanup::<PauseCleanupMarker>)
+            );
+    }
+}
+
+#[derive(Component)]
+struct PauseCleanupMarker;
+
+#[derive(Component, Copy, Clone)]
+enum PauseButton {
+    Resume,
+    Restart,
+    Options,
+    Quit,
+}
+
+// anything that leaves the level has to wait until we're back in InGame
+// since the pause state sits on top of it
+#[derive(Default, Resource)]
+struct PauseState {
+    paused_animations: Vec::<Entity>,
+    pending: Option::<PauseButton>,
+}
+
+fn pause_game(
+    mut state: ResMut<State<AppState>>,
+    game_state: Res<game_state::GameState>,
+    mut players: Query<&mut ActionState<player::PlayerAction>>,
+) {
+    if game_state.level_ended {
+        return;
+    }
+
+    if consume_pause(&mut players) {
+        state.push(AppState::Pause).unwrap();
+    }
+}
+
+// the new state runs in the same frame so the press has to be used up
+// or the pause menu would see it too and close straight away
+fn consume_pause(players: &mut Query<&mut ActionState<player::PlayerAction>>) -> bool {
+    let mut pressed = false;
+    for mut action_state in players.iter_mut() {
+        if action_state.just_pressed(player::PlayerAction::Pause) {
+            action_state.consume(player::PlayerAction::Pause);
+            pressed = true;
+        }
+    }
+    pressed
+}
+
+fn handle_pause_exit(
+    mut pause_state: ResMut<PauseState>,
+    mut assets_handler: asset_loading::AssetsHandler,
+    mut game_assets: ResMut<GameAssets>,
+    game_state: ResMut<game_state::GameState>,
+) {
+    match pause_state.pending.take() {
+        Some(PauseButton::Restart) => assets_handler.skip_load(AppState::LevelOver),
+        Some(PauseButton::Quit) => assets_handler.load(AppState::TitleScreen, &mut game_assets, &game_state),
+        _ => (),
+    }
+}
+
+fn setup(
+    mut commands: Commands,
+    game_assets: Res<GameAssets>,
+    mut pause_state: ResMut<PauseState>,
+    mut rapier: ResMut<RapierConfiguration>,
+    mut animations: Query<(Entity, &mut AnimationPlayer)>,
+    text_scaler: text_size::TextScaler,
+    mut game_state: ResMut<game_state::GameState>,
+) {
+    // left over from starting the level, nothing ticks it down in game
+    game_state.title_screen_cooldown = 0.0;
+    rapier.physics_pipeline_active = false;
+    pause_state.paused_animations = vec!();
+    for (entity, mut animation) in &mut animations {
+        if !animation.is_paused() {
+            animation.pause();
+            pause_state.paused_animations.push(entity);
+        }
+    }
+
+    commands
+        .spawn(InputManagerBundle {
+            input_map: MenuAction::default_input_map(),
+            action_state: ActionState::default(),
+        })
+        .insert(PauseCleanupMarker);
+
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                position_type: PositionType::Absolute,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            background_color: Color::rgba(0.0, 0.0, 0.0, 0.5).into(),
+            ..Default::default()
+        })
+        .insert(PauseCleanupMarker)
+        .with_children(|parent| {
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        size: Size::new(Val::Percent(30.0), Val::Percent(50.0)),
+                        justify_content: JustifyContent::Center,
+                        flex_direction: FlexDirection::ColumnReverse,
+                        align_items: AlignItems::Center,
+                        ..Default::default()
+                    },
+                    background_color: menus::MENU_BACKGROUND.into(),
+                    ..Default::default()
+                })
+                .with_children(|parent| {
+                    parent.spawn(TextBundle {
+                        text: Text::from_section(
+                            "Paused",
+                            TextStyle {
+                                font: game_assets.font.clone(),
+                                font_size: text_scaler.scale(menus::DEFAULT_FONT_SIZE * 0.6),
+                                color: Color::rgb(0.0, 0.0, 0.0),
+                            }
+                        ),
+                        ..Default::default()
+                    });
+
+                    let font_size = text_scaler.scale(menus::BUTTON_LABEL_FONT_SIZE);
+                    menus::spawn_button(parent, game_assets.font.clone(), font_size, 20.0, "Resume", PauseButton::Resume);
+                    menus::spawn_button(parent, game_assets.font.clone(), font_size, 20.0, "Restart Level", PauseButton::Restart);
+                    menus::spawn_button(parent, game_assets.font.clone(), font_size, 20.0, "Options", PauseButton::Options);
+                    menus::spawn_button(parent, game_assets.font.clone(), font_size, 20.0, "Quit to Title", PauseButton::Quit);
+                });
+        });
+}
+
+fn unfreeze(
+    mut pause_state: ResMut<PauseState>,
+    mut rapier: ResMut<RapierConfiguration>,
+    mut animations: Query<&mut AnimationPlayer>,
+) {
+    rapier.physics_pipeline_active = true;
+    for entity in pause_state.paused_animations.drain(..) {
+        if let Ok(mut animation) = animations.get_mut(entity) {
+            animation.resume();
+        }
+    }
+}
+
+fn update_menu_buttons(
+    mut selected_button: Local<usize>,
+    mut state: ResMut<State<AppState>>,
+    mut pause_state: ResMut<PauseState>,
+    buttons: Query<&PauseButton, With<Button>>,
+    mut button_colors: Query<&mut BackgroundColor, (With<Button>, With<PauseButton>)>,
+    action_state: Query<&ActionState<MenuAction>, With<PauseCleanupMarker>>,
+    mut players: Query<&mut ActionState<player::PlayerAction>>,
+    mut game_state: ResMut<game_state::GameState>,
+    mut audio: GameAudio,
+    game_assets: Res<GameAssets>,
+    time: Res<Time>,
+) {
+    // coming back from options in the same frame its back button was picked
+    if game_state.title_screen_cooldown > 0.0 {
+        game_state.title_screen_cooldown -= time.delta_seconds();
+        return;
+    }
+
+    let action_state = match action_state.get_single() {
+        Ok(action_state) => action_state,
+        Err(_) => return,
+    };
+    let number_of_buttons = buttons.iter().count();
+    if number_of_buttons == 0 {
+        return;
+    }
+
+    // hitting pause again is the same as picking resume
+    if consume_pause(&mut players) {
+        *selected_button = 0;
+        state.pop().unwrap();
+        return;
+    }
+
+    if action_state.just_pressed(MenuAction::Up) {
+        audio.play_sfx(&game_assets.blip);
+        *selected_button = selected_button
+            .checked_sub(1)
+            .unwrap_or(number_of_buttons - 1);
+    }
+    if action_state.just_pressed(MenuAction::Down) {
+        audio.play_sfx(&game_assets.blip);
+        *selected_button = (*selected_button + 1) % number_of_buttons;
+    }
+
+    for (i, mut color) in button_colors.iter_mut().enumerate() {
+        if i == *selected_button {
+            *color = menus::HOVERED_BUTTON.into();
+        } else {
+            *color = menus::NORMAL_BUTTON.into();
+        }
+    }
+
+    if action_state.just_pressed(MenuAction::Select) {
+        audio.play_sfx(&game_assets.blip);
+        let pause_button = buttons.iter().nth(*selected_button).copied();
+        if let Some(PauseButton::Options) = pause_button {
+            // options sits on top of the pause menu and pops back to it
+            state.push(AppState::Options).unwrap();
+        } else if pause_button.is_some() {
+            pause_state.pending = pause_button;
+            *selected_button = 0;
+            state.pop().unwrap();
+        }
+    }
+}
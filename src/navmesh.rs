@@ -0,0 +1,443 @@
+use bevy::prelude::*;
+use bevy::render::primitives::Aabb;
+use bevy_rapier3d::prelude::*;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+
+use crate::{bull::Bull, ingame, AppState};
+
+const CELL_SIZE: f32 = 1.0;
+// how far the middle of the shopkeeper and fishmonger has to stay from the furniture,
+// the bulls get grids of their own sized to their collider
+const NPC_CLEARANCE: f32 = 1.0;
+// obstacles are looked for between these heights so the floor doesn't count
+const FLOOR_CLEARANCE: f32 = 0.5;
+const OBSTACLE_HEIGHT: f32 = 2.5;
+// gives rapier a moment to pick up the level's colliders
+const BUILD_DELAY: f32 = 0.25;
+// how often cells under furniture that got moved are checked again
+const UPDATE_RATE: f32 = 0.5;
+// furniture has to move this far before its cells are checked again
+const MOVED_DISTANCE: f32 = 0.1;
+// how far to look for somewhere to stand when the goal is inside a shelf
+const SNAP_CELLS: i32 = 10;
+const WAYPOINT_RADIUS: f32 = 1.0;
+const REPATH_DISTANCE: f32 = 2.0;
+const REPATH_TIME: f32 = 1.0;
+
+const NEIGHBORS: [(i32, i32, u32); 8] = [
+    (1, 0, 10), (-1, 0, 10), (0, 1, 10), (0, -1, 10),
+    (1, 1, 14), (1, -1, 14), (-1, 1, 14), (-1, -1, 14),
+];
+
+pub struct NavMeshPlugin;
+impl Plugin for NavMeshPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<NavMesh>()
+            .add_system_set(SystemSet::on_enter(AppState::InGame).with_system(reset))
+            .add_system_set(
+                SystemSet::on_update(AppState::InGame)
+                    .with_system(build_navmesh)
+                    .with_system(update_navmesh.after(build_navmesh))
+            );
+    }
+}
+
+// the levels are flat shops so this is a grid of walkable cells over the
+// floor rather than polygons. cells are numbered x + z * width
+#[derive(Default, Resource)]
+pub struct NavMesh {
+    origin: Vec2,
+    width: usize,
+    height: usize,
+    // one per size of thing walking around, smallest first
+    grids: Vec::<Grid>,
+    // the floor each piece of furniture covered when its cells were last checked
+    footprints: HashMap::<Entity, (Vec2, Vec2)>,
+    // floor that furniture moved off of or onto since the last update
+    dirty: Vec::<(Vec2, Vec2)>,
+    build_cooldown: Option::<f32>,
+    update_cooldown: f32,
+}
+
+struct Grid {
+    clearance: f32,
+    walkable: Vec::<bool>,
+    // cells that can reach each other share a region, the insides of
+    // shelves end up as little islands of their own
+    regions: Vec::<usize>,
+}
+
+impl NavMesh {
+    pub fn is_built(&self) -> bool {
+        !self.grids.is_empty()
+    }
+
+    // the tightest grid that still fits, or the roomiest one if nothing does
+    fn grid(&self, clearance: f32) -> &Grid {
+        self.grids.iter()
+                  .find(|grid| grid.clearance >= clearance)
+                  .unwrap_or_else(|| self.grids.last().unwrap())
+    }
+
+    fn cell(&self, position: Vec3) -> (i32, i32) {
+        (((position.x - self.origin.x) / CELL_SIZE).floor() as i32,
+         ((position.z - self.origin.y) / CELL_SIZE).floor() as i32)
+    }
+
+    fn index(&self, (x, z): (i32, i32)) -> Option::<usize> {
+        if x < 0 || z < 0 || x as usize >= self.width || z as usize >= self.height {
+            None
+        } else {
+            Some(x as usize + z as usize * self.width)
+        }
+    }
+
+    fn center(&self, index: usize) -> Vec2 {
+        let x = (index % self.width) as f32;
+        let z = (index / self.width) as f32;
+        self.origin + Vec2::new(x + 0.5, z + 0.5) * CELL_SIZE
+    }
+
+    fn is_walkable(&self, grid: &Grid, cell: (i32, i32)) -> bool {
+        self.index(cell).map(|i| grid.walkable[i]).unwrap_or(false)
+    }
+
+    // closest walkable cell to the position, only in the region if there is one
+    fn nearest_walkable(&self, grid: &Grid, position: Vec3, region: Option::<usize>) -> Option::<usize> {
+        let (x, z) = self.cell(position);
+        let position = Vec2::new(position.x, position.z);
+        for radius in 0..=SNAP_CELLS {
+            let nearest = (-radius..=radius)
+                .flat_map(|dx| (-radius..=radius).map(move |dz| (x + dx, z + dz)))
+                .filter(|(cx, cz)| (cx - x).abs() == radius || (cz - z).abs() == radius)
+                .filter_map(|cell| self.index(cell))
+                .filter(|i| grid.walkable[*i] && region.map(|r| grid.regions[*i] == r).unwrap_or(true))
+                .min_by(|a, b| self.center(*a).distance(position).total_cmp(&self.center(*b).distance(position)));
+            if nearest.is_some() {
+                return nearest;
+            }
+        }
+        None
+    }
+
+    // walks the line between two points a half cell at a time
+    fn can_see(&self, grid: &Grid, from: Vec2, to: Vec2) -> bool {
+        let steps = (from.distance(to) / (CELL_SIZE * 0.5)).ceil() as usize;
+        (0..=steps).all(|step| {
+            let point = from.lerp(to, step as f32 / steps.max(1) as f32);
+            self.is_walkable(grid, self.cell(Vec3::new(point.x, 0.0, point.y)))
+        })
+    }
+
+    // waypoints to follow from one spot to another, not including where it starts, for
+    // something that needs clearance around its middle. if the goal isn't somewhere
+    // that can be stood on the path ends as close as it can
+    pub fn find_path(&self, from: Vec3, to: Vec3, clearance: f32) -> Option::<Vec::<Vec3>> {
+        if !self.is_built() {
+            return None;
+        }
+
+        let grid = self.grid(clearance);
+        let start = self.nearest_walkable(grid, from, None)?;
+        let goal = self.nearest_walkable(grid, to, Some(grid.regions[start]))?;
+        let goal_cell = ((goal % self.width) as i32, (goal / self.width) as i32);
+
+        let heuristic = |index: usize| {
+            let dx = ((index % self.width) as i32 - goal_cell.0).unsigned_abs();
+            let dz = ((index / self.width) as i32 - goal_cell.1).unsigned_abs();
+            10 * dx.max(dz) + 4 * dx.min(dz)
+        };
+
+        let mut costs = vec![u32::MAX; grid.walkable.len()];
+        let mut came_from = vec![usize::MAX; grid.walkable.len()];
+        let mut open = BinaryHeap::new();
+        costs[start] = 0;
+        open.push(Reverse((heuristic(start), start)));
+
+        while let Some(Reverse((_, current))) = open.pop() {
+            if current == goal {
+                break;
+            }
+
+            let (x, z) = ((current % self.width) as i32, (current / self.width) as i32);
+            for (dx, dz, step_cost) in NEIGHBORS {
+                // no cutting corners around the end of a shelf
+                if !self.is_walkable(grid, (x + dx, z)) || !self.is_walkable(grid, (x, z + dz)) {
+                    continue;
+                }
+                let next = match self.index((x + dx, z + dz)) {
+                    Some(next) if grid.walkable[next] => next,
+                    _ => continue,
+                };
+
+                let cost = costs[current] + step_cost;
+                if cost < costs[next] {
+                    costs[next] = cost;
+                    came_from[next] = current;
+                    open.push(Reverse((cost + heuristic(next), next)));
+                }
+            }
+        }
+
+        if costs[goal] == u32::MAX {
+            return None;
+        }
+
+        let mut cells = vec!(goal);
+        while let Some(previous) = cells.last().map(|i| came_from[*i]).filter(|i| *i != usize::MAX) {
+            cells.push(previous);
+        }
+        cells.reverse();
+
+        // skip every cell that can be walked past in a straight line
+        let mut waypoints = vec!();
+        let mut anchor = Vec2::new(from.x, from.z);
+        for pair in cells.windows(2) {
+            if !self.can_see(grid, anchor, self.center(pair[1])) {
+                anchor = self.center(pair[0]);
+                waypoints.push(Vec3::new(anchor.x, from.y, anchor.y));
+            }
+        }
+
+        let end = if self.cell(to) == goal_cell {
+            to
+        } else {
+            let end = self.center(goal);
+            Vec3::new(end.x, to.y, end.y)
+        };
+        waypoints.push(end);
+
+        Some(waypoints)
+    }
+
+    fn is_clear(&self, rapier_context: &RapierContext, filter: QueryFilter, clearance: f32, index: usize) -> bool {
+        let center = self.center(index);
+        let shape = Collider::cuboid(clearance, (OBSTACLE_HEIGHT - FLOOR_CLEARANCE) / 2.0, clearance);
+        let shape_y = (OBSTACLE_HEIGHT + FLOOR_CLEARANCE) / 2.0;
+        rapier_context.intersection_with_shape(Vec3::new(center.x, shape_y, center.y), Quat::IDENTITY, &shape, filter)
+                      .is_none()
+    }
+}
+
+impl Grid {
+    // flood fill each bit of floor that can be walked around
+    fn find_regions(&mut self, navmesh: &NavMesh) {
+        let mut regions = vec![usize::MAX; self.walkable.len()];
+        let mut region = 0;
+        for start in 0..regions.len() {
+            if !self.walkable[start] || regions[start] != usize::MAX {
+                continue;
+            }
+
+            regions[start] = region;
+            let mut open = VecDeque::from([start]);
+            while let Some(current) = open.pop_front() {
+                let (x, z) = ((current % navmesh.width) as i32, (current / navmesh.width) as i32);
+                for (dx, dz, _) in NEIGHBORS.iter().take(4) {
+                    if let Some(next) = navmesh.index((x + dx, z + dz)) {
+                        if self.walkable[next] && regions[next] == usize::MAX {
+                            regions[next] = region;
+                            open.push_back(next);
+                        }
+                    }
+                }
+            }
+            region += 1;
+        }
+        self.regions = regions;
+    }
+}
+
+// something walking along a path that gets planned again as its goal moves around
+#[derive(Default)]
+pub struct Path {
+    // anything left at 0.0 walks on the tightest grid
+    clearance: f32,
+    waypoints: Vec::<Vec3>,
+    goal: Option::<Vec3>,
+    repath_cooldown: f32,
+}
+
+impl Path {
+    pub fn new(clearance: f32) -> Self {
+        Path {
+            clearance,
+            ..default()
+        }
+    }
+
+    // the spot to head for right now, straight at the goal if there's no way around
+    pub fn steer(&mut self, navmesh: &NavMesh, position: Vec3, goal: Vec3, delta: f32) -> Vec3 {
+        self.repath_cooldown -= delta;
+        let goal_moved = self.goal.map(|g| g.distance(goal) > REPATH_DISTANCE).unwrap_or(true);
+        if goal_moved || self.repath_cooldown <= 0.0 {
+            self.waypoints = navmesh.find_path(position, goal, self.clearance).unwrap_or_default();
+            self.goal = Some(goal);
+            self.repath_cooldown = REPATH_TIME;
+        }
+
+        while self.waypoints.len() > 1 && flat_distance(self.waypoints[0], position) < WAYPOINT_RADIUS {
+            self.waypoints.remove(0);
+        }
+
+        self.waypoints.first().copied().unwrap_or(goal)
+    }
+
+    // whether it's as close to the goal as it's going to get
+    pub fn reached_end(&self, position: Vec3, distance: f32) -> bool {
+        self.waypoints.len() == 1 && flat_distance(self.waypoints[0], position) < distance
+    }
+
+    pub fn clear(&mut self) {
+        *self = Path::new(self.clearance);
+    }
+}
+
+fn flat_distance(a: Vec3, b: Vec3) -> f32 {
+    Vec2::new(a.x, a.z).distance(Vec2::new(b.x, b.z))
+}
+
+// the bit of floor under something's bounding box
+fn footprint(transform: &GlobalTransform, aabb: &Aabb) -> (Vec2, Vec2) {
+    let mut min = Vec2::splat(f32::MAX);
+    let mut max = Vec2::splat(f32::MIN);
+    for corner in 0..8 {
+        let offset = Vec3::new(
+            if corner & 1 == 0 { -1.0 } else { 1.0 },
+            if corner & 2 == 0 { -1.0 } else { 1.0 },
+            if corner & 4 == 0 { -1.0 } else { 1.0 },
+        ) * Vec3::from(aabb.half_extents);
+        let corner = transform.transform_point(Vec3::from(aabb.center) + offset);
+        min = min.min(Vec2::new(corner.x, corner.z));
+        max = max.max(Vec2::new(corner.x, corner.z));
+    }
+    (min, max)
+}
+
+fn reset(
+    mut navmesh: ResMut<NavMesh>,
+) {
+    *navmesh = NavMesh::default();
+}
+
+fn build_navmesh(
+    time: Res<Time>,
+    mut navmesh: ResMut<NavMesh>,
+    rapier_context: Res<RapierContext>,
+    added: Query<Entity, Added<ingame::BullCollide>>,
+    added_bulls: Query<Entity, Added<Bull>>,
+    bulls: Query<&Bull>,
+    bull_collides: Query<Entity, (With<ingame::BullCollide>, With<Collider>)>,
+    bounds: Query<(Entity, &GlobalTransform, &Aabb), With<ingame::BullCollide>>,
+) {
+    // starts over whenever more of the level shows up
+    if !added.is_empty() || !added_bulls.is_empty() {
+        navmesh.build_cooldown = Some(BUILD_DELAY);
+    }
+    let build_cooldown = match navmesh.build_cooldown {
+        Some(build_cooldown) => build_cooldown - time.delta_seconds(),
+        None => return,
+    };
+    if build_cooldown > 0.0 {
+        navmesh.build_cooldown = Some(build_cooldown);
+        return;
+    }
+
+    let footprints = bounds.iter()
+                           .map(|(entity, transform, aabb)| (entity, footprint(transform, aabb)))
+                           .collect::<HashMap::<_, _>>();
+    let (min, max) = footprints.values()
+                               .fold((Vec2::splat(f32::MAX), Vec2::splat(f32::MIN)),
+                                     |(min, max), (a, b)| (min.min(*a), max.max(*b)));
+    if min.x > max.x {
+        navmesh.build_cooldown = None;
+        return;
+    }
+
+    let mut clearances = vec!(NPC_CLEARANCE);
+    clearances.extend(bulls.iter().map(|bull| bull.archetype.clearance()));
+    clearances.sort_by(|a, b| a.total_cmp(b));
+    clearances.dedup_by(|a, b| (*a - *b).abs() < CELL_SIZE * 0.1);
+
+    let width = ((max.x - min.x) / CELL_SIZE).ceil() as usize;
+    let height = ((max.y - min.y) / CELL_SIZE).ceil() as usize;
+    let is_obstacle = |entity: Entity| bull_collides.get(entity).is_ok();
+    let filter = QueryFilter::default().exclude_sensors().predicate(&is_obstacle);
+
+    *navmesh = NavMesh {
+        origin: min,
+        width,
+        height,
+        footprints,
+        ..default()
+    };
+    for clearance in clearances {
+        let mut grid = Grid {
+            clearance,
+            walkable: (0..width * height).map(|i| navmesh.is_clear(&rapier_context, filter, clearance, i))
+                                         .collect(),
+            regions: vec!(),
+        };
+        grid.find_regions(&navmesh);
+
+        println!("Built navmesh {}x{} for clearance {} with {} walkable cells", width, height, clearance,
+                 grid.walkable.iter().filter(|w| **w).count());
+        navmesh.grids.push(grid);
+    }
+}
+
+// shoved and restored furniture changes where things can walk, so the cells
+// it moved off of and onto get checked again
+fn update_navmesh(
+    time: Res<Time>,
+    mut navmesh: ResMut<NavMesh>,
+    rapier_context: Res<RapierContext>,
+    bull_collides: Query<Entity, (With<ingame::BullCollide>, With<Collider>)>,
+    moved: Query<(Entity, &GlobalTransform, &Aabb), (With<ingame::BullCollide>, Changed<GlobalTransform>)>,
+) {
+    if !navmesh.is_built() || navmesh.build_cooldown.is_some() {
+        return;
+    }
+
+    for (entity, transform, aabb) in &moved {
+        let (min, max) = footprint(transform, aabb);
+        match navmesh.footprints.get(&entity).copied() {
+            // small wobbles are left to add up until they're worth checking
+            Some((previous_min, previous_max))
+                if previous_min.distance(min) < MOVED_DISTANCE && previous_max.distance(max) < MOVED_DISTANCE => continue,
+            Some(previous) => navmesh.dirty.push(previous),
+            None => (),
+        }
+        navmesh.dirty.push((min, max));
+        navmesh.footprints.insert(entity, (min, max));
+    }
+
+    navmesh.update_cooldown -= time.delta_seconds();
+    if navmesh.dirty.is_empty() || navmesh.update_cooldown > 0.0 {
+        return;
+    }
+    navmesh.update_cooldown = UPDATE_RATE;
+
+    let is_obstacle = |entity: Entity| bull_collides.get(entity).is_ok();
+    let filter = QueryFilter::default().exclude_sensors().predicate(&is_obstacle);
+    let dirty = std::mem::take(&mut navmesh.dirty);
+    let mut grids = std::mem::take(&mut navmesh.grids);
+    for grid in grids.iter_mut() {
+        for (min, max) in dirty.iter() {
+            // anything within clearance of the furniture can be affected
+            let margin = grid.clearance + CELL_SIZE;
+            let (min_x, min_z) = navmesh.cell(Vec3::new(min.x - margin, 0.0, min.y - margin));
+            let (max_x, max_z) = navmesh.cell(Vec3::new(max.x + margin, 0.0, max.y + margin));
+            for z in min_z..=max_z {
+                for x in min_x..=max_x {
+                    if let Some(index) = navmesh.index((x, z)) {
+                        grid.walkable[index] = navmesh.is_clear(&rapier_context, filter, grid.clearance, index);
+                    }
+                }
+            }
+        }
+        grid.find_regions(&navmesh);
+    }
+    navmesh.grids = grids;
+}
@@ -0,0 +1,153 @@
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+use std::f32::consts::PI;
+
+use crate::{
+    bull::Bull,
+    bull_mind,
+    ingame,
+    player,
+    AppState,
+};
+
+// sight lines are checked at this height so shelves block them and the floor and tables don't
+const SIGHT_HEIGHT: f32 = 2.0;
+const FOOTSTEP_RATE: f32 = 0.4;
+const FOOTSTEP_SPEED: f32 = 5.0;
+// how loud things are, as a share of a bull's hearing range
+pub const BREAK_LOUDNESS: f32 = 1.0;
+pub const CAPE_LOUDNESS: f32 = 0.6;
+const FOOTSTEP_LOUDNESS: f32 = 0.2;
+const DIVE_LOUDNESS: f32 = 0.4;
+
+pub struct PerceptionPlugin;
+impl Plugin for PerceptionPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_set(
+            SystemSet::on_update(AppState::InGame)
+                .with_system(make_footstep_noise)
+                .with_system(perceive.after(make_footstep_noise).before(bull_mind::update_bull_minds))
+        )
+        .add_event::<NoiseEvent>();
+    }
+}
+
+pub struct NoiseEvent {
+    pub position: Vec3,
+    pub loudness: f32,
+}
+
+// what a bull can actually see and hear, nothing else gets to know where the player is
+#[derive(Component)]
+pub struct Perception {
+    pub vision_range: f32,
+    // either side of straight ahead, in radians
+    pub vision_angle: f32,
+    pub hearing_range: f32,
+    // how long it keeps after someone it's lost sight of
+    pub memory: f32,
+    // players it can see right now
+    pub in_sight: Vec::<Entity>,
+    // where whoever it's chasing was last seen and how long ago
+    pub last_seen: Option::<(Vec3, f32)>,
+}
+
+impl Default for Perception {
+    fn default() -> Self {
+        Perception {
+            vision_range: 70.0,
+            vision_angle: PI / 3.0,
+            hearing_range: 60.0,
+            memory: 3.0,
+            in_sight: vec!(),
+            last_seen: None,
+        }
+    }
+}
+
+impl Perception {
+    pub fn can_see(&self, player: Entity) -> bool {
+        self.in_sight.contains(&player)
+    }
+
+    // where it thinks whoever it's chasing is, if it still remembers
+    pub fn target(&self) -> Option::<Vec3> {
+        self.last_seen
+            .filter(|(_, age)| *age <= self.memory)
+            .map(|(position, _)| position)
+    }
+}
+
+fn make_footstep_noise(
+    time: Res<Time>,
+    mut players: Query<(&Transform, &Velocity, &mut player::Player)>,
+    mut noise_event_writer: EventWriter<NoiseEvent>,
+) {
+    for (transform, velocity, mut player) in &mut players {
+        player.footstep_cooldown -= time.delta_seconds();
+        if velocity.linvel.length() < FOOTSTEP_SPEED || player.footstep_cooldown > 0.0 {
+            continue;
+        }
+
+        noise_event_writer.send(NoiseEvent {
+            position: transform.translation,
+            loudness: if player.state == player::PlayerState::Diving { DIVE_LOUDNESS } else { FOOTSTEP_LOUDNESS },
+        });
+        player.footstep_cooldown = FOOTSTEP_RATE;
+    }
+}
+
+fn perceive(
+    time: Res<Time>,
+    rapier_context: Res<RapierContext>,
+    mut bulls: Query<(&Transform, &mut Bull, &mut Perception)>,
+    players: Query<(Entity, &Transform), (With<player::Player>, Without<Bull>)>,
+    obstacles: Query<Entity, With<ingame::BullCollide>>,
+    mut noise_event_reader: EventReader<NoiseEvent>,
+) {
+    let noises = noise_event_reader.iter().collect::<Vec::<_>>();
+    let is_obstacle = |entity: Entity| obstacles.get(entity).is_ok();
+    let filter = QueryFilter::default().exclude_sensors().predicate(&is_obstacle);
+
+    for (transform, mut bull, mut perception) in &mut bulls {
+        let position = transform.translation;
+        let eye = Vec3::new(position.x, SIGHT_HEIGHT, position.z);
+        // the bull model faces down its x axis
+        let forward = transform.right();
+
+        let (vision_range, vision_angle) = (perception.vision_range, perception.vision_angle);
+        let in_sight = players.iter()
+            .filter(|(_, player_transform)| {
+                let to_player = Vec3::new(player_transform.translation.x, SIGHT_HEIGHT, player_transform.translation.z) - eye;
+                let distance = to_player.length();
+                if distance > vision_range {
+                    return false;
+                }
+                if distance > 0.0 && forward.angle_between(to_player) > vision_angle {
+                    return false;
+                }
+
+                rapier_context.cast_ray(eye, to_player.normalize_or_zero(), distance, true, filter).is_none()
+            })
+            .map(|(entity, _)| entity)
+            .collect();
+        perception.in_sight = in_sight;
+
+        if let Some((_, age)) = perception.last_seen.as_mut() {
+            *age += time.delta_seconds();
+        }
+        if let Some(chasing) = bull.chasing.filter(|chasing| perception.can_see(*chasing)) {
+            if let Ok((_, player_transform)) = players.get(chasing) {
+                perception.last_seen = Some((player_transform.translation, 0.0));
+            }
+        }
+
+        // the loudest thing it can hear wins
+        let heard = noises.iter()
+                          .filter(|noise| noise.position.distance(position) < perception.hearing_range * noise.loudness)
+                          .max_by(|a, b| a.loudness.total_cmp(&b.loudness));
+        if let Some(noise) = heard {
+            bull.noise = Some((noise.position, 0.0));
+        }
+    }
+}
@@ -6,10 +6,11 @@ use crate::{
     AppState,
     ZeroSignum,
     bull,
+    perception,
 };
 use bevy::prelude::*;
 use leafwing_input_manager::prelude::*;
-use rand::Rng;
+use serde::{Deserialize, Serialize};
 use std::f32::consts::TAU;
 use std::collections::HashMap;
 use bevy_rapier3d::prelude::*;
@@ -43,21 +44,22 @@ pub struct Player {
     pub state: PlayerState,
     pub dive_cooldown: f32,
     pub hit_cooldown: f32,
+    pub footstep_cooldown: f32,
 }
 
 impl Player {
     pub fn new() -> Self {
-        let mut rng = rand::thread_rng();
-
         Player {
             speed: 40.0,
             rotation_speed: 1.0,
             friction: 0.10,
-            random: rng.gen_range(0.5..1.0),
+            // replay::seed_spawned picks the real one
+            random: 0.75,
             current_animation: Handle::<AnimationClip>::default(),
             state: PlayerState::Normal,
             dive_cooldown: 0.0,
             hit_cooldown: 0.0,
+            footstep_cooldown: 0.0,
         }
     }
 }
@@ -244,9 +246,16 @@ pub fn move_player(
 
         match player.state {
              PlayerState::Charging => {
-                 for (bull_transform, _) in &bull {
+                 // face the bull that's after us, or the closest one
+                 let player_translation = transform.translation;
+                 let facing = bull.iter()
+                                  .min_by(|(a_transform, a), (b_transform, b)| {
+                                      (a.chasing != Some(entity)).cmp(&(b.chasing != Some(entity)))
+                                          .then(a_transform.translation.distance(player_translation)
+                                                .total_cmp(&b_transform.translation.distance(player_translation)))
+                                  });
+                 if let Some((bull_transform, _)) = facing {
                      let bull_translation = bull_transform.translation;
-                     let player_translation = transform.translation;
                      let angle = (-(bull_translation.z - player_translation.z))
                             .atan2(bull_translation.x - player_translation.x);
                      let rotation = Quat::from_axis_angle(Vec3::Y, angle);
@@ -268,7 +277,7 @@ pub fn move_player(
     }
 }
 
-#[derive(Actionlike, PartialEq, Eq, Clone, Copy, Hash, Debug)]
+#[derive(Actionlike, PartialEq, Eq, Clone, Copy, Hash, Debug, Serialize, Deserialize)]
 pub enum PlayerAction {
     Up,
     Down,
@@ -279,6 +288,8 @@ pub enum PlayerAction {
     ActionDown,
     ActionLeft,
     ActionRight,
+
+    Pause,
 }
 
 impl PlayerAction {
@@ -363,11 +374,14 @@ impl PlayerBundle {
         input_map.insert(KeyCode::Return, ActionRight);
         input_map.insert(KeyCode::Space, ActionRight);
 
+        input_map.insert(KeyCode::Escape, Pause);
+        input_map.insert(GamepadButtonType::Start, Pause);
+
         input_map
     }
 }
 
-fn handle_controllers(
+pub fn handle_controllers(
     controllers: Res<game_controller::GameController>,
     game_state: Res<game_state::GameState>,
     mut players: Query<(Entity, &mut ActionState<PlayerAction>), With<Player>>,
@@ -414,6 +428,11 @@ fn handle_controllers(
             } else {
                 action_state.release(PlayerAction::ActionRight);
             }
+            if pressed.contains(&game_controller::GameButton::Start) {
+                action_state.press(PlayerAction::Pause);
+            } else {
+                action_state.release(PlayerAction::Pause);
+            }
         }
 
         for (_, just_pressed) in controllers.just_pressed.iter() {
@@ -441,12 +460,13 @@ pub enum Movement {
     Normal(direction::Direction),
 }
 
-fn handle_input(
+pub fn handle_input(
     mut app_state: ResMut<State<AppState>>,
     mut players: Query<(Entity, &ActionState<PlayerAction>, &Transform, &mut Player, &mut Velocity)>,
     game_state: Res<game_state::GameState>,
     mut player_move_event_writer: EventWriter<PlayerMoveEvent>,
     mut bull_charge_event_writer: EventWriter<bull::BullChargeEvent>,
+    mut noise_event_writer: EventWriter<perception::NoiseEvent>,
 ) {
     for (entity, action_state, transform, mut player, mut velocity) in &mut players {
         //println!("T: {:?}", transform.translation);
@@ -469,7 +489,15 @@ fn handle_input(
         if action_state.pressed(PlayerAction::ActionUp) {}
 
         if action_state.just_pressed(PlayerAction::ActionRight) {
-            bull_charge_event_writer.send(bull::BullChargeEvent { charging: true });
+            bull_charge_event_writer.send(bull::BullChargeEvent {
+                charging: true,
+                player: entity,
+                bull: None,
+            });
+            noise_event_writer.send(perception::NoiseEvent {
+                position: transform.translation,
+                loudness: perception::CAPE_LOUDNESS,
+            });
         }
 
         if action_state.pressed(PlayerAction::ActionRight) {
@@ -5,9 +5,11 @@ use bevy::ecs::component::StorageType;
 use crate::{
     assets,
     game_state,
+    groups,
     AppState,
     ingame,
     fishmonger,
+    perception,
     audio::GameAudio,
 };
 use bevy::gltf::Gltf;
@@ -32,7 +34,9 @@ pub trait ComponentAdder {
     fn add_components(entity_commands: &mut EntityCommands, mesh: Option<&Mesh>);
 }
 
-pub struct BreakEvent;
+pub struct BreakEvent {
+    pub entity: Entity,
+}
 
 #[derive(Component)]
 pub struct Breakable {
@@ -45,6 +49,17 @@ pub enum BreakableType {
     FishBowl,
 }
 
+impl BreakableType {
+    // the catalogue price, levels can override it by putting a Value in the node name
+    pub fn value(&self) -> u32 {
+        match self {
+            BreakableType::Plate => 35,
+            BreakableType::Mug => 20,
+            BreakableType::FishBowl => 2500,
+        }
+    }
+}
+
 #[derive(Component)]
 pub struct Plate;
 #[derive(Component)]
@@ -106,6 +121,7 @@ impl ComponentAdder for Plate {
             .insert(Breakable {
                 breakable_type: BreakableType::Plate,
             })
+            .insert(groups::Value(BreakableType::Plate.value()))
             .insert(Plate)
             .insert(ingame::CleanupMarker);
         add_dynamic_rapier_components_for_props(entity_commands);
@@ -131,6 +147,7 @@ impl ComponentAdder for Mug {
             .insert(Breakable {
                 breakable_type: BreakableType::Mug,
             })
+            .insert(groups::Value(BreakableType::Mug.value()))
             .insert(ingame::CleanupMarker)
             .insert(Mug);
         add_dynamic_rapier_components_for_props(entity_commands);
@@ -157,6 +174,7 @@ impl ComponentAdder for FishBowl {
             .insert(Breakable {
                 breakable_type: BreakableType::FishBowl,
             })
+            .insert(groups::Value(BreakableType::FishBowl.value()))
             .insert(FishBowl)
             .insert(ingame::CleanupMarker);
         add_dynamic_rapier_components_for_props(entity_commands);
@@ -198,6 +216,7 @@ fn handle_breakables(
     game_assets: Res<assets::GameAssets>,
     mut break_event_writer: EventWriter<BreakEvent>,
     mut chase_event_writer: EventWriter<fishmonger::ChaseEvent>,
+    mut noise_event_writer: EventWriter<perception::NoiseEvent>,
     mut audio: GameAudio,
 ) {
     for e in contact_force_events.iter() {
@@ -205,8 +224,14 @@ fn handle_breakables(
         [e.collider1, e.collider2].iter()
             .for_each(|entity| {
                 if let Ok((breakable, transform, velocity)) = breakables.get(*entity) {
-                    break_event_writer.send(BreakEvent);
+                    break_event_writer.send(BreakEvent {
+                        entity: *entity,
+                    });
                     audio.play_sfx(&game_assets.break_sfx);
+                    noise_event_writer.send(perception::NoiseEvent {
+                        position: transform.translation(),
+                        loudness: perception::BREAK_LOUDNESS,
+                    });
 
                     let mut entity_commands = commands.entity(*entity);
                     remove_dynamic_rapier_components_for_props(&mut entity_commands);
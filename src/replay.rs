@@ -0,0 +1,236 @@
+use crate::{arcade, bull, game_script, player, player::PlayerAction, save, AppState};
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use leafwing_input_manager::prelude::*;
+use rand::{rngs::StdRng, Rng, RngCore, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+pub const LAST_REPLAY_FILE: &str = "last.replay.ron";
+
+pub struct ReplayPlugin;
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GameRng>()
+            .init_resource::<Replays>()
+            .add_system_set(SystemSet::on_enter(AppState::InGame).with_system(start))
+            .add_system_set(
+                SystemSet::on_update(AppState::InGame)
+                    .with_system(seed_spawned)
+                    .with_system(play_tick.after(player::handle_controllers).before(player::handle_input))
+                    .with_system(record_tick.after(play_tick).before(player::handle_input))
+            )
+            .add_system_set(SystemSet::on_exit(AppState::InGame).with_system(finish));
+    }
+}
+
+// anything that changes how a level plays out pulls from here instead of
+// thread_rng so the same seed gets the same bull. dust, fire and camera
+// shake are just for looks and don't need to
+#[derive(Resource)]
+pub struct GameRng {
+    rng: StdRng,
+}
+
+impl Default for GameRng {
+    fn default() -> Self {
+        GameRng {
+            rng: StdRng::seed_from_u64(0),
+        }
+    }
+}
+
+impl GameRng {
+    pub fn reseed(&mut self, seed: u64) {
+        self.rng = StdRng::seed_from_u64(seed);
+    }
+}
+
+impl RngCore for GameRng {
+    fn next_u32(&mut self) -> u32 {
+        self.rng.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.rng.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.rng.fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.rng.try_fill_bytes(dest)
+    }
+}
+
+// a tick is one InGame frame rather than a fixed step, the game and rapier run on the
+// frame's delta so each one is kept and played back as is. pausing isn't recorded and
+// the console stays shut during playback, so nothing outside the level gets between ticks
+#[derive(Serialize, Deserialize, Default, Clone)]
+#[serde(default)]
+pub struct Replay {
+    pub chapter: String,
+    pub seed: u64,
+    pub arcade_round: Option::<usize>,
+    // how long each tick took in nanoseconds so playback sees the same frame times
+    pub ticks: Vec::<u64>,
+    // only the ticks where what's held down changed
+    pub inputs: Vec::<(usize, Vec::<PlayerAction>)>,
+}
+
+#[derive(Default, Resource)]
+pub struct Replays {
+    recording: Replay,
+    playing: Option::<Replay>,
+    // set by the console, picked up once the level starts
+    pub pending: Option::<Replay>,
+    tick: usize,
+    held: Vec::<PlayerAction>,
+    // when the last tick played, a fast machine would otherwise play it back in fast forward
+    last_played: Option::<Instant>,
+}
+
+impl Replays {
+    pub fn is_playing(&self) -> bool {
+        self.playing.is_some()
+    }
+}
+
+fn start(
+    mut replays: ResMut<Replays>,
+    mut game_rng: ResMut<GameRng>,
+    game_script_state: Res<game_script::GameScriptState>,
+    arcade: Res<arcade::Arcade>,
+) {
+    replays.tick = 0;
+    replays.held.clear();
+    replays.last_played = None;
+    replays.playing = replays.pending.take();
+
+    let seed = match &replays.playing {
+        Some(replay) => {
+            println!("Playing back {} with seed {}", replay.chapter, replay.seed);
+            replay.seed
+        },
+        None => rand::thread_rng().gen(),
+    };
+    game_rng.reseed(seed);
+
+    replays.recording = Replay {
+        chapter: game_script_state.current.clone(),
+        seed,
+        arcade_round: if arcade.active { Some(arcade.round) } else { None },
+        ..default()
+    };
+}
+
+// the scene hook can't get at resources so these spawn with a placeholder
+fn seed_spawned(
+    mut game_rng: ResMut<GameRng>,
+    mut bulls: Query<&mut bull::Bull, Added<bull::Bull>>,
+    mut players: Query<&mut player::Player, Added<player::Player>>,
+) {
+    for mut bull in &mut bulls {
+        bull.random = game_rng.gen_range(0.5..1.0);
+    }
+    for mut player in &mut players {
+        player.random = game_rng.gen_range(0.5..1.0);
+    }
+}
+
+fn play_tick(
+    mut commands: Commands,
+    mut replays: ResMut<Replays>,
+    time: Res<Time>,
+    mut time_update_strategy: ResMut<TimeUpdateStrategy>,
+    mut players: Query<(Entity, &mut ActionState<PlayerAction>, Option::<&InputMap<PlayerAction>>), With<player::Player>>,
+) {
+    let replays = &mut *replays;
+    let replay = match &replays.playing {
+        Some(replay) => replay,
+        None => return,
+    };
+
+    let tick = replays.tick;
+    if let (Some(last_played), Some(nanos)) = (replays.last_played, replay.ticks.get(tick)) {
+        let recorded = Duration::from_nanos(*nanos);
+        let elapsed = last_played.elapsed();
+        if elapsed < recorded {
+            std::thread::sleep(recorded - elapsed);
+        }
+    }
+    replays.last_played = Some(Instant::now());
+
+    if let Ok(i) = replay.inputs.binary_search_by_key(&tick, |(tick, _)| *tick) {
+        replays.held = replay.inputs[i].1.clone();
+    }
+
+    for (entity, mut action_state, input_map) in &mut players {
+        // otherwise the keyboard fights the replay for the action state
+        if input_map.is_some() {
+            commands.entity(entity).remove::<InputMap<PlayerAction>>();
+        }
+
+        for action in PlayerAction::variants() {
+            let held = replays.held.contains(&action);
+            if held && !action_state.pressed(action) {
+                action_state.press(action);
+            } else if !held && action_state.pressed(action) {
+                action_state.release(action);
+            }
+        }
+    }
+
+    // the next frame gets the same delta it had when it was recorded
+    match replay.ticks.get(tick + 1) {
+        Some(nanos) => {
+            let last_update = time.last_update().unwrap_or_else(|| time.startup());
+            *time_update_strategy = TimeUpdateStrategy::ManualInstant(last_update + Duration::from_nanos(*nanos));
+        },
+        None => {
+            println!("Replay finished");
+            *time_update_strategy = TimeUpdateStrategy::Automatic;
+            replays.held.clear();
+            replays.playing = None;
+        },
+    }
+}
+
+fn record_tick(
+    mut replays: ResMut<Replays>,
+    time: Res<Time>,
+    players: Query<&ActionState<PlayerAction>, With<player::Player>>,
+) {
+    let held = players.iter()
+                      .next()
+                      // a pause in the middle of playback would throw the ticks out
+                      .map(|action_state| PlayerAction::variants()
+                                                      .filter(|action| *action != PlayerAction::Pause && action_state.pressed(*action))
+                                                      .collect())
+                      .unwrap_or_else(Vec::<PlayerAction>::new);
+
+    let tick = replays.tick;
+    let recording = &mut replays.recording;
+    recording.ticks.push(time.delta().as_nanos() as u64);
+    let changed = recording.inputs.last()
+                                  .map(|(_, last)| *last != held)
+                                  .unwrap_or(!held.is_empty());
+    if changed {
+        recording.inputs.push((tick, held));
+    }
+    replays.tick += 1;
+}
+
+fn finish(
+    mut replays: ResMut<Replays>,
+    mut time_update_strategy: ResMut<TimeUpdateStrategy>,
+) {
+    *time_update_strategy = TimeUpdateStrategy::Automatic;
+    // a playback would just write out the same thing it read, and one
+    // that's about to start shouldn't have its file written over
+    if replays.playing.take().is_none() && replays.pending.is_none() {
+        println!("Writing replay of {} ({} ticks)", replays.recording.chapter, replays.recording.ticks.len());
+        save::write_file(LAST_REPLAY_FILE, &replays.recording);
+    }
+}
@@ -0,0 +1,204 @@
+use bevy::prelude::*;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::collections::HashMap;
+use crate::{game_script, game_state};
+
+pub const SLOT_COUNT: usize = 3;
+const PROGRESS_FILE: &str = "progress.ron";
+
+pub struct SavePlugin;
+impl Plugin for SavePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(SaveSlots::load())
+           .insert_resource(read_file::<Progress>(PROGRESS_FILE).unwrap_or_default())
+           .add_system(autosave);
+    }
+}
+
+#[derive(Serialize, Deserialize, Default, Clone)]
+#[serde(default)]
+pub struct SaveData {
+    pub chapter: String,
+    pub best_scores: HashMap::<String, f32>,
+    pub score: f32,
+    pub score_check_count: usize,
+    // bumped on every save so Continue can tell which slot is the newest
+    pub save_count: u64,
+}
+
+// active is None when playing from the level select so it doesn't
+// write over anyone's story
+#[derive(Resource)]
+pub struct SaveSlots {
+    pub active: Option::<usize>,
+    pub slots: Vec::<Option::<SaveData>>,
+}
+
+impl SaveSlots {
+    fn load() -> Self {
+        SaveSlots {
+            active: None,
+            slots: (0..SLOT_COUNT).map(|i| read_file(&slot_file(i))).collect(),
+        }
+    }
+
+    pub fn latest(&self) -> Option::<usize> {
+        self.slots.iter()
+                  .enumerate()
+                  .filter_map(|(i, slot)| slot.as_ref().map(|save| (i, save.save_count)))
+                  .max_by_key(|(_, save_count)| *save_count)
+                  .map(|(i, _)| i)
+    }
+
+    // first empty slot, otherwise the one that hasn't been played in the longest
+    pub fn free_slot(&self) -> usize {
+        self.slots.iter()
+                  .position(|slot| slot.is_none())
+                  .or_else(|| self.slots.iter()
+                                        .enumerate()
+                                        .min_by_key(|(_, slot)| slot.as_ref().map(|save| save.save_count))
+                                        .map(|(i, _)| i))
+                  .unwrap_or(0)
+    }
+
+    pub fn new_game(
+        &mut self,
+        slot: usize,
+        game_script_state: &mut game_script::GameScriptState,
+        game_state: &mut game_state::GameState,
+    ) {
+        println!("Starting new game in slot {}", slot);
+        self.active = Some(slot);
+        self.slots[slot] = Some(SaveData::default());
+        game_script_state.current = String::new();
+        game_state.score = 1.0;
+        game_state.score_check_count = 0;
+    }
+
+    pub fn load_game(
+        &mut self,
+        slot: usize,
+        game_script_state: &mut game_script::GameScriptState,
+        game_state: &mut game_state::GameState,
+    ) {
+        if let Some(save) = &self.slots[slot] {
+            println!("Loading slot {} at {:?}", slot, save.chapter);
+            self.active = Some(slot);
+            game_script_state.current = save.chapter.clone();
+            game_state.score = save.score;
+            game_state.score_check_count = save.score_check_count;
+        } else {
+            self.new_game(slot, game_script_state, game_state);
+        }
+    }
+
+    pub fn record_level(&mut self, chapter: &str, score: f32) {
+        if let Some(save) = self.active.and_then(|active| self.slots[active].as_mut()) {
+            let best = save.best_scores.entry(chapter.to_string()).or_insert(0.0);
+            *best = best.max(score);
+        }
+    }
+}
+
+// shared between all the slots, this is what the level select reads
+#[derive(Serialize, Deserialize, Default, Resource)]
+#[serde(default)]
+pub struct Progress {
+    pub unlocked: Vec::<String>,
+    pub best_scores: HashMap::<String, f32>,
+}
+
+impl Progress {
+    pub fn is_unlocked(&self, level: &str) -> bool {
+        self.unlocked.iter().any(|unlocked| unlocked == level)
+    }
+
+    pub fn complete_level(&mut self, level: &str, score: f32, next_level: Option::<&str>) {
+        let best = self.best_scores.entry(level.to_string()).or_insert(0.0);
+        *best = best.max(score);
+        if let Some(next_level) = next_level {
+            if !self.is_unlocked(next_level) {
+                println!("Unlocked {}", next_level);
+                self.unlocked.push(next_level.to_string());
+            }
+        }
+        write_file(PROGRESS_FILE, self);
+    }
+}
+
+// write the active slot out whenever the story moves on
+fn autosave(
+    mut save_slots: ResMut<SaveSlots>,
+    game_script_state: Res<game_script::GameScriptState>,
+    game_state: Res<game_state::GameState>,
+) {
+    if !game_script_state.is_changed() || game_script_state.current.is_empty() {
+        return;
+    }
+    let active = match save_slots.active {
+        Some(active) => active,
+        None => return,
+    };
+
+    let save_count = save_slots.slots.iter()
+                                     .flatten()
+                                     .map(|save| save.save_count)
+                                     .max()
+                                     .unwrap_or(0) + 1;
+    let save = save_slots.slots[active].get_or_insert_with(SaveData::default);
+    save.chapter = game_script_state.current.clone();
+    save.score = game_state.score;
+    save.score_check_count = game_state.score_check_count;
+    save.save_count = save_count;
+
+    write_file(&slot_file(active), save);
+}
+
+fn slot_file(slot: usize) -> String {
+    format!("slot_{}.ron", slot)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn data_dir() -> Option::<std::path::PathBuf> {
+    directories::ProjectDirs::from("", "", "bull_cliche").map(|dirs| dirs.data_dir().to_path_buf())
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn read_file<T: DeserializeOwned>(name: &str) -> Option::<T> {
+    let path = data_dir()?.join(name);
+    let contents = std::fs::read_to_string(&path).ok()?;
+    match ron::from_str(&contents) {
+        Ok(data) => Some(data),
+        Err(e) => {
+            println!("couldn't read {:?}: {}", path, e);
+            None
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn write_file<T: Serialize>(name: &str, data: &T) {
+    let dir = match data_dir() {
+        Some(dir) => dir,
+        None => {
+            println!("no data directory, not writing {}", name);
+            return;
+        }
+    };
+
+    let contents = ron::ser::to_string_pretty(data, ron::ser::PrettyConfig::default())
+                        .expect("Couldn't serialize save data");
+    if let Err(e) = std::fs::create_dir_all(&dir).and_then(|_| std::fs::write(dir.join(name), contents)) {
+        println!("couldn't write {}: {}", name, e);
+    }
+}
+
+// no filesystem in the browser, everything just lasts for the session
+#[cfg(target_arch = "wasm32")]
+pub fn read_file<T: DeserializeOwned>(_name: &str) -> Option::<T> {
+    None
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn write_file<T: Serialize>(_name: &str, _data: &T) {
+}
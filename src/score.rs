@@ -1,21 +1,36 @@
 use bevy::prelude::*;
 use crate::{
     AppState, groups, game_state, asset_loading, game_script, assets, cutscene,
-    follow_text, player,
+    follow_text, player, save, arcade, leaderboard, replay,
 };
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 pub const BREAK_DISTANCE: f32 = 0.5;
 pub struct ScorePlugin;
 impl Plugin for ScorePlugin {
     fn build(&self, app: &mut App) {
-        app.add_system_set(SystemSet::on_update(AppState::InGame)
+        app.init_resource::<DamageReports>()
+           .add_system_set(SystemSet::on_enter(AppState::InGame).with_system(reset_damage_reports))
+           .add_system_set(SystemSet::on_update(AppState::InGame)
            .with_system(track_round_time)
-           .with_system(check_score)
+           .with_system(check_score.after(groups::track_group_status))
+           .with_system(count_repairs)
         );
     }
 }
 
+// which groups have popped up how much damage so far this level
+#[derive(Default, Resource)]
+struct DamageReports {
+    changed_groups: HashSet::<usize>,
+    reported_damage: HashMap::<usize, u32>,
+}
+
+fn reset_damage_reports(
+    mut damage_reports: ResMut<DamageReports>,
+) {
+    *damage_reports = DamageReports::default();
+}
 
 fn track_round_time(
     mut game_state: ResMut<game_state::GameState>,
@@ -24,6 +39,12 @@ fn track_round_time(
     mut game_script_state: ResMut<game_script::GameScriptState>,
     mut assets_handler: asset_loading::AssetsHandler,
     mut game_assets: ResMut<assets::GameAssets>,
+    campaigns: Res<Assets<game_script::Campaign>>,
+    mut arcade: ResMut<arcade::Arcade>,
+    mut save_slots: ResMut<save::SaveSlots>,
+    mut progress: ResMut<save::Progress>,
+    mut leaderboards: ResMut<leaderboard::Leaderboards>,
+    replays: Res<replay::Replays>,
     mut cooldown: Local<f32>,
     mut follow_text_event_writer: EventWriter<follow_text::FollowTextEvent>,
     players: Query<Entity, With<player::Player>>,
@@ -33,8 +54,28 @@ fn track_round_time(
         if game_state.level_end_cooldown <= 0.0 {
             game_state.level_ended = false;
             cutscene_state.cutscene_index = 0;
-            game_script_state.next();
-            assets_handler.load(AppState::Cutscene, &mut game_assets, &game_state);
+            if !replays.is_playing() {
+                leaderboards.record(&game_script_state.current, 
+                                    leaderboard::Mode::current(&arcade, &save_slots),
+                                    leaderboard::LeaderboardEntry::from_round(&game_state));
+            }
+            if arcade.active {
+                // arcade runs skip the story and keep going until everything is destroyed
+                if game_state.live_score <= 0.0 {
+                    assets_handler.skip_load(AppState::ArcadeOver);
+                } else if let Some(campaign) = campaigns.get(&game_assets.campaign) {
+                    arcade.next_round(game_state.live_score, campaign, &mut game_script_state);
+                    assets_handler.load(AppState::LoadWorld, &mut game_assets, &game_state);
+                }
+                return;
+            }
+
+            save_slots.record_level(&game_script_state.current, game_state.live_score);
+            if let Some(campaign) = campaigns.get(&game_assets.campaign) {
+                let next_level = campaign.next_level(&game_script_state.current).map(|c| c.id.as_str());
+                progress.complete_level(&game_script_state.current, game_state.live_score, next_level);
+            }
+            assets_handler.skip_load(AppState::ScoreDisplay);
         }
     } else {
         game_state.current_time -= time.delta_seconds();
@@ -66,11 +107,22 @@ fn track_round_time(
 }
 
 fn check_score(
-    group_members: Query<(&groups::GroupMember, &Transform, &GlobalTransform)>,
+    status: Res<groups::GroupStatus>,
     mut cooldown: Local<f32>,
+    mut damage_reports: ResMut<DamageReports>,
     mut game_state: ResMut<game_state::GameState>,
+    mut follow_text_event_writer: EventWriter<follow_text::FollowTextEvent>,
     time: Res<Time>,
 ) {
+    game_state.groups_broken = status.damaged_count();
+    game_state.damage = status.damage.round() as u32;
+    game_state.total_value = status.total_value;
+    if status.total_value > 0 {
+        game_state.live_score = 1.0 - (status.damage / status.total_value as f32);
+    }
+    damage_reports.changed_groups.extend(status.changed().iter().copied());
+
+    // the running average and the damage pop ups are sampled once a second
     *cooldown -= time.delta_seconds();
     *cooldown = cooldown.clamp(0.0, 10.0);
     
@@ -79,42 +131,57 @@ fn check_score(
     }
     *cooldown = 1.0;
 
-    let grouped_translations = 
-        group_members.iter()
-                     .fold(HashMap::<usize, Vec::<(Vec3, Vec3, Vec3)>>::new(),
-                     |mut acc, (group_member, transform, global_transform)| {
-                         acc.entry(group_member.group_id)
-                            .or_insert(vec!())
-                            .push((transform.translation, group_member.original_transform.translation, global_transform.translation()));
-
-                         acc
-                     });
-
-    let mut group_count = 0;
-    let mut group_broken_count = 0;
-    for (_, translations) in grouped_translations.iter() {
-        group_count += 1; 
-
-        for (current, original, _) in translations.iter() {
-            let distance = (current.y - original.y).abs();
-            if distance > BREAK_DISTANCE {
-                group_broken_count += 1;
-                break;
-            }
-        }
-    }
-
-    if group_count > 0 {
+    if status.total_value > 0 {
         game_state.score_check_count += 1;
-        let current = 1.0 - (group_broken_count as f32 / group_count as f32);
-        game_state.live_score = current;
         // add as a running average of the score
-        game_state.score = (game_state.score * (game_state.score_check_count - 1) as f32 + current) 
+        game_state.score = (game_state.score * (game_state.score_check_count - 1) as f32 + game_state.live_score) 
                             / game_state.score_check_count as f32;
-        game_state.live_score = current;
-//        println!("Score {} {} ( {} / {} )", game_state.live_score, game_state.score, group_broken_count, group_count);
+//        println!("Score {} {} ( ${} / ${} )", game_state.live_score, game_state.score, game_state.damage, game_state.total_value);
     }
-}
 
+    let DamageReports { changed_groups, reported_damage } = &mut *damage_reports;
+    for group_id in changed_groups.drain() {
+        let group = match status.group(group_id) {
+            Some(group) if group.is_damaged() => group,
+            _ => {
+                reported_damage.remove(&group_id);
+                continue;
+            }
+        };
 
+        // only pop up what got worse since the last check
+        let group_damage = group.damaged_value.round() as u32;
+        let reported = reported_damage.entry(group_id).or_insert(0);
+        if group_damage > *reported {
+            follow_text_event_writer.send(follow_text::FollowTextEvent {
+                follow: follow_text::FollowThing::Spot(group.position),
+                text: format!("-{}", dollars(group_damage - *reported)),
+                color: Color::RED,
+                time_to_live: 3.0,
+            });
+        }
+        *reported = group_damage;
+    }
+}
+
+// 2500 -> $2,500
+pub fn dollars(amount: u32) -> String {
+    let digits = amount.to_string();
+    let with_commas = digits.chars()
+                            .enumerate()
+                            .fold(String::new(), |mut acc, (i, c)| {
+                                if i > 0 && (digits.len() - i) % 3 == 0 {
+                                    acc.push(',');
+                                }
+                                acc.push(c);
+                                acc
+                            });
+    format!("${}", with_commas)
+}
 
+fn count_repairs(
+    mut restore_group_event_reader: EventReader<groups::RestoreGroupEvent>,
+    mut game_state: ResMut<game_state::GameState>,
+) {
+    game_state.groups_repaired += restore_group_event_reader.iter().count();
+}
@@ -0,0 +1,276 @@
+use crate::{
+    asset_loading, assets::GameAssets, audio::GameAudio, cleanup, game_script, game_state, leaderboard,
+    menus, menus::name_entry::{NameEntry, NameInput}, score, title_screen::MenuAction, ui::text_size,
+    AppState,
+};
+use bevy::prelude::*;
+use leafwing_input_manager::prelude::*;
+
+const STAR_COUNT: usize = 5;
+const STAR_SIZE: f32 = 80.0;
+
+pub struct ScoreDisplayPlugin;
+impl Plugin for ScoreDisplayPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<NameEntry>()
+            .add_system_set(SystemSet::on_enter(AppState::ScoreDisplay).with_system(setup))
+            .add_system_set(
+                SystemSet::on_update(AppState::ScoreDisplay)
+                    .with_system(update_menu_buttons)
+                    .with_system(update_name_text.after(update_menu_buttons))
+            )
+            .add_system_set(
+                SystemSet::on_exit(AppState::ScoreDisplay)
+                    .with_system(cleanup::<ScoreDisplayCleanupMarker>)
+            );
+    }
+}
+
+#[derive(Component)]
+struct ScoreDisplayCleanupMarker;
+
+#[derive(Component)]
+struct NameText;
+
+#[derive(Component, Copy, Clone)]
+enum ScoreDisplayButton {
+    Retry,
+    Continue,
+}
+
+// score is 0.0 - 1.0, rounded to the nearest half star
+fn star_images(score: f32, game_assets: &GameAssets) -> Vec::<Handle<Image>> {
+    let stars = (score.clamp(0.0, 1.0) * STAR_COUNT as f32 * 2.0).round() / 2.0;
+    (0..STAR_COUNT).map(|i| {
+                       let star = i as f32;
+                       if stars >= star + 1.0 {
+                           game_assets.star_full_texture.image.clone()
+                       } else if stars >= star + 0.5 {
+                           game_assets.star_half_texture.image.clone()
+                       } else {
+                           game_assets.star_empty_texture.image.clone()
+                       }
+                   })
+                   .collect()
+}
+
+fn setup(
+    mut commands: Commands,
+    game_assets: Res<GameAssets>,
+    mut game_state: ResMut<game_state::GameState>,
+    leaderboards: Res<leaderboard::Leaderboards>,
+    mut name_entry: ResMut<NameEntry>,
+    text_scaler: text_size::TextScaler,
+) {
+    game_state.title_screen_cooldown = 1.0;
+    // the round was recorded when the level ended, this is just to put a name on it
+    *name_entry = NameEntry::new(&leaderboards.last_name, leaderboards.has_unnamed());
+
+    commands
+        .spawn(InputManagerBundle {
+            input_map: MenuAction::default_input_map(),
+            action_state: ActionState::default(),
+        })
+        .insert(ScoreDisplayCleanupMarker);
+
+    let time_remaining = game_state.current_time.max(0.0);
+    let stats = [
+        format!("Time Left: {:0>2}:{:0>2}", (time_remaining / 60.0) as usize, (time_remaining % 60.0) as usize),
+        format!("Broken: {}", game_state.groups_broken),
+        format!("Damage: -{} of {}", score::dollars(game_state.damage), score::dollars(game_state.total_value)),
+        format!("Repaired: {}", game_state.groups_repaired),
+    ];
+    let text_style = TextStyle {
+        font: game_assets.font.clone(),
+        font_size: text_scaler.scale(menus::BUTTON_LABEL_FONT_SIZE),
+        color: Color::WHITE,
+    };
+
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                position_type: PositionType::Absolute,
+                justify_content: JustifyContent::Center,
+                flex_direction: FlexDirection::ColumnReverse,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            background_color: Color::NONE.into(),
+            ..Default::default()
+        })
+        .insert(ScoreDisplayCleanupMarker)
+        .with_children(|parent| {
+            parent.spawn(TextBundle {
+                text: Text::from_section(
+                    "Results",
+                    TextStyle {
+                        font: game_assets.font.clone(),
+                        font_size: text_scaler.scale(menus::DEFAULT_FONT_SIZE),
+                        color: Color::WHITE,
+                    }
+                ),
+                ..Default::default()
+            });
+
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        margin: UiRect::all(Val::Percent(2.0)),
+                        flex_direction: FlexDirection::Row,
+                        ..Default::default()
+                    },
+                    background_color: Color::NONE.into(),
+                    ..Default::default()
+                })
+                .with_children(|parent| {
+                    for image in star_images(game_state.score, &game_assets) {
+                        parent.spawn(ImageBundle {
+                            style: Style {
+                                size: Size::new(Val::Px(text_scaler.scale(STAR_SIZE)), Val::Px(text_scaler.scale(STAR_SIZE))),
+                                ..Default::default()
+                            },
+                            image: image.into(),
+                            ..Default::default()
+                        });
+                    }
+                });
+
+            for stat in stats {
+                parent.spawn(TextBundle {
+                    text: Text::from_section(stat, text_style.clone()),
+                    ..Default::default()
+                });
+            }
+
+            parent
+                .spawn(TextBundle {
+                    style: Style {
+                        margin: UiRect::all(Val::Percent(1.0)),
+                        ..Default::default()
+                    },
+                    text: Text::from_section("", text_style.clone()),
+                    ..Default::default()
+                })
+                .insert(NameText);
+
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        size: Size::new(Val::Percent(30.0), Val::Percent(20.0)),
+                        margin: UiRect::all(Val::Percent(2.0)),
+                        justify_content: JustifyContent::Center,
+                        flex_direction: FlexDirection::ColumnReverse,
+                        align_items: AlignItems::Center,
+                        ..Default::default()
+                    },
+                    background_color: menus::MENU_BACKGROUND.into(),
+                    ..Default::default()
+                })
+                .with_children(|parent| {
+                    let font_size = text_scaler.scale(menus::BUTTON_LABEL_FONT_SIZE);
+                    menus::spawn_button(parent, game_assets.font.clone(), font_size, 40.0, "Continue", ScoreDisplayButton::Continue);
+                    menus::spawn_button(parent, game_assets.font.clone(), font_size, 40.0, "Retry", ScoreDisplayButton::Retry);
+                });
+        });
+}
+
+fn update_menu_buttons(
+    mut selected_button: Local<usize>,
+    buttons: Query<&ScoreDisplayButton, With<Button>>,
+    mut button_colors: Query<&mut BackgroundColor, With<Button>>,
+    action_state: Query<&ActionState<MenuAction>>,
+    mut audio: GameAudio,
+    mut assets_handler: asset_loading::AssetsHandler,
+    mut game_assets: ResMut<GameAssets>,
+    mut game_state: ResMut<game_state::GameState>,
+    mut game_script_state: ResMut<game_script::GameScriptState>,
+    campaigns: Res<Assets<game_script::Campaign>>,
+    mut name_entry: ResMut<NameEntry>,
+    mut leaderboards: ResMut<leaderboard::Leaderboards>,
+    time: Res<Time>,
+) {
+    // players are usually still mashing when the level ends
+    if game_state.title_screen_cooldown > 0.0 {
+        game_state.title_screen_cooldown -= time.delta_seconds();
+        return;
+    }
+
+    let action_state = match action_state.get_single() {
+        Ok(action_state) => action_state,
+        Err(_) => return,
+    };
+
+    if name_entry.entering {
+        match name_entry.handle_input(action_state) {
+            NameInput::Nothing => (),
+            NameInput::Changed => audio.play_sfx(&game_assets.blip),
+            NameInput::Confirmed => {
+                audio.play_sfx(&game_assets.blip);
+                leaderboards.name_unnamed(&name_entry.name());
+                game_state.title_screen_cooldown = 0.3;
+            },
+        }
+        return;
+    }
+
+    let number_of_buttons = buttons.iter().count();
+    if number_of_buttons == 0 {
+        return;
+    }
+
+    if action_state.just_pressed(MenuAction::Up) {
+        audio.play_sfx(&game_assets.blip);
+        *selected_button = selected_button
+            .checked_sub(1)
+            .unwrap_or(number_of_buttons - 1);
+    }
+    if action_state.just_pressed(MenuAction::Down) {
+        audio.play_sfx(&game_assets.blip);
+        *selected_button = (*selected_button + 1) % number_of_buttons;
+    }
+
+    for (i, mut color) in button_colors.iter_mut().enumerate() {
+        if i == *selected_button {
+            *color = menus::HOVERED_BUTTON.into();
+        } else {
+            *color = menus::NORMAL_BUTTON.into();
+        }
+    }
+
+    if action_state.just_pressed(MenuAction::Select) {
+        audio.play_sfx(&game_assets.blip);
+        match buttons.iter().nth(*selected_button) {
+            Some(ScoreDisplayButton::Continue) => {
+                if let Some(campaign) = campaigns.get(&game_assets.campaign) {
+                    *selected_button = 0;
+                    let next_state = game_script_state.next(campaign);
+                    assets_handler.load(next_state, &mut game_assets, &game_state);
+                }
+            },
+            Some(ScoreDisplayButton::Retry) => {
+                *selected_button = 0;
+                assets_handler.skip_load(AppState::LevelOver);
+            },
+            None => (),
+        }
+    }
+}
+
+fn update_name_text(
+    name_entry: Res<NameEntry>,
+    mut texts: Query<&mut Text, With<NameText>>,
+) {
+    if !name_entry.is_changed() {
+        return;
+    }
+
+    for mut text in &mut texts {
+        text.sections[0].value =
+            if name_entry.entering {
+                format!("New high score! {}", name_entry.display())
+            } else {
+                String::new()
+            };
+    }
+}
@@ -1,9 +1,8 @@
 use bevy::prelude::*;
 use bevy::ecs::system::EntityCommands;
 use crate::{
-    AppState, assets::GameAssets, game_state, groups, ZeroSignum, dust, score, follow_text,
+    AppState, assets::GameAssets, game_state, groups, navmesh, ZeroSignum, dust, follow_text,
 };
-use std::collections::HashMap;
 
 const DUST_RATE: f32 = 0.2;
 const REPAIR_TIME: f32 = 5.0;
@@ -12,19 +11,19 @@ impl Plugin for ShopKeeperPlugin {
     fn build(&self, app: &mut App) {
         app.add_system_set(
             SystemSet::on_update(AppState::InGame)
-                .with_system(think_shopkeepers)
+                .with_system(think_shopkeepers.after(groups::track_group_status))
                 .with_system(move_shopkeepers)
             );
     }
 }
 
-enum ShopKeeperState {
+pub enum ShopKeeperState {
     Normal,
     Repairing(usize, f32),
 }
 
 #[derive(Component)]
-struct ShopKeeper {
+pub struct ShopKeeper {
     pub speed: f32,
     pub rotation_speed: f32,
     pub friction: f32,
@@ -35,6 +34,8 @@ struct ShopKeeper {
     pub current_animation: Handle<AnimationClip>,
     pub target: Option::<(usize, Vec3)>,
     pub initial_position: Option::<Vec3>,
+    pub repair_time: f32,
+    pub path: navmesh::Path,
 }
 
 impl Default for ShopKeeper {
@@ -50,6 +51,8 @@ impl Default for ShopKeeper {
             dust_cooldown: 0.0,
             target: None,
             initial_position: None,
+            repair_time: REPAIR_TIME,
+            path: navmesh::Path::default(),
         }
     }
 }
@@ -67,6 +70,7 @@ fn move_shopkeepers(
     mut animations: Query<&mut AnimationPlayer>,
     mut game_state: ResMut<game_state::GameState>,
     time: Res<Time>,
+    navmesh: Res<navmesh::NavMesh>,
     game_assets: ResMut<GameAssets>,
     mut restore_group_event_writer: EventWriter<groups::RestoreGroupEvent>,
     mut dust_spawn_event_writer: EventWriter<dust::DustSpawnEvent>,
@@ -109,17 +113,20 @@ fn move_shopkeepers(
 
         if let Some((group_id, target)) = keeper.target {
             let target = Vec3::new(target.x, 0.0, target.z);
-            if keeper_transform.translation.distance(target) < 0.8 {
+            // groups usually sit on shelves so get as close as the navmesh allows
+            if keeper_transform.translation.distance(target) < 0.8
+            || keeper.path.reached_end(keeper_transform.translation, 0.8) {
+                keeper.path.clear();
 
                 // println!("AT TARGET {:?} {:?}", keeper_transform.translation, target);
-                keeper.state = ShopKeeperState::Repairing(group_id, REPAIR_TIME);
+                keeper.state = ShopKeeperState::Repairing(group_id, keeper.repair_time);
                 dust_spawn_event_writer.send(dust::DustSpawnEvent {
                     position: keeper_transform.translation,
                     count: 3,
                     spread: 6.0,
                     rate: 0.5,
                     dust_time_to_live: 3.0,
-                    emitter_time_to_live: REPAIR_TIME,
+                    emitter_time_to_live: keeper.repair_time,
                     size: 2.0,
                     image: game_assets.cloud_texture.image.clone(),
                     ..default()
@@ -131,13 +138,15 @@ fn move_shopkeepers(
                     speed: 2.0,
                     rate: 0.2,
                     dust_time_to_live: 3.0,
-                    emitter_time_to_live: REPAIR_TIME,
+                    emitter_time_to_live: keeper.repair_time,
                     size: 1.5,
                     image: game_assets.wrench_texture.image.clone(),
                     ..default()
                 });
             } else {
                 //println!("Moving to target? {} to {}", keeper_transform.translation, target);
+                let translation = keeper_transform.translation;
+                let target = keeper.path.steer(&navmesh, translation, target, time.delta_seconds());
                 let direction = target - keeper_transform.translation;
                 let acceleration = Vec3::from(direction);
 
@@ -191,7 +200,7 @@ fn move_shopkeepers(
 fn think_shopkeepers(
     mut shopkeepers: Query<(Entity, &mut ShopKeeper)>,
     time: Res<Time>,
-    group_members: Query<(&groups::GroupMember, &Transform, &GlobalTransform)>,
+    group_status: Res<groups::GroupStatus>,
     mut follow_text_event_writer: EventWriter<follow_text::FollowTextEvent>,
 ) {
     for (entity, mut keeper) in &mut shopkeepers {
@@ -204,31 +213,18 @@ fn think_shopkeepers(
 
         if keeper.cleanup_cooldown <= 0.0 {
 
-            let grouped_translations = 
-                group_members.iter()
-                             .fold(HashMap::<usize, Vec::<(Vec3, Vec3, Vec3)>>::new(),
-                             |mut acc, (group_member, transform, global_transform)| {
-                                 acc.entry(group_member.group_id)
-                                    .or_insert(vec!())
-                                    .push((transform.translation, group_member.original_transform.translation, global_transform.translation()));
-
-                                 acc
-                             });
-            'outer: for (group_id, translations) in grouped_translations.iter() {
-                for (current, original, target) in translations.iter() {
-                    let distance = (current.y - original.y).abs();
-                    if distance > score::BREAK_DISTANCE {
-                        println!("setting target! {}", target);
-                        keeper.target = Some((*group_id, target.clone()));
-                        follow_text_event_writer.send(follow_text::FollowTextEvent {
-                            follow: follow_text::FollowThing::Entity(entity),
-                            text: "I can fix that!".to_string(),
-                            color: Color::WHITE,
-                            time_to_live: 6.0,
-                        });
-                        break 'outer;
-                    }
-                }
+            // go for whatever is in the worst shape
+            let worst = group_status.damaged_groups()
+                                    .max_by(|(_, a), (_, b)| a.percent().total_cmp(&b.percent()));
+            if let Some((group_id, group)) = worst {
+                println!("setting target! {} ({:.0}% damaged)", group.position, group.percent() * 100.0);
+                keeper.target = Some((group_id, group.position));
+                follow_text_event_writer.send(follow_text::FollowTextEvent {
+                    follow: follow_text::FollowThing::Entity(entity),
+                    text: "I can fix that!".to_string(),
+                    color: Color::WHITE,
+                    time_to_live: 6.0,
+                });
             }
 
             keeper.cleanup_cooldown = 10.0;
@@ -0,0 +1,169 @@
+use bevy::prelude::*;
+use bevy_mod_outline::{OutlineBundle, OutlineVolume};
+use bevy_rapier3d::prelude::*;
+use std::collections::HashSet;
+
+use crate::{
+    bull::{self, Bull, BullState},
+    groups,
+    ingame,
+    perception::Perception,
+    AppState,
+};
+
+const LANE_WIDTH: f32 = 3.0;
+const MAX_LANE_LENGTH: f32 = 80.0;
+// the cast is kept up off the floor so it only finds the furniture
+const CAST_RADIUS: f32 = 1.0;
+const MIN_CAST_HEIGHT: f32 = 1.5;
+// the pile up at the end of the run catches a little past where it hits
+const CRASH_DEPTH: f32 = 2.0;
+const LANE_HEIGHT: f32 = 0.05;
+
+pub struct TelegraphPlugin;
+impl Plugin for TelegraphPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_set(
+            SystemSet::on_update(AppState::InGame)
+                .with_system(spawn_telegraphs)
+                .with_system(update_telegraphs.after(spawn_telegraphs))
+        );
+    }
+}
+
+// the lane on the floor showing where a charging bull is about to run
+#[derive(Component)]
+struct Telegraph {
+    bull: Entity,
+}
+
+#[derive(Component)]
+struct Telegraphed {
+    // whatever outline it had before the lane went over it
+    previous: Option::<OutlineVolume>,
+}
+
+fn threat_outline() -> OutlineVolume {
+    OutlineVolume {
+        visible: true,
+        width: 4.0,
+        colour: Color::ORANGE_RED,
+    }
+}
+
+fn spawn_telegraphs(
+    mut commands: Commands,
+    bulls: Query<Entity, Added<Bull>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    for entity in &bulls {
+        commands
+            .spawn(PbrBundle {
+                mesh: meshes.add(Mesh::from(shape::Plane { size: 1.0 })),
+                material: materials.add(StandardMaterial {
+                    base_color: Color::rgba(1.0, 0.2, 0.1, 0.4),
+                    alpha_mode: AlphaMode::Blend,
+                    unlit: true,
+                    ..default()
+                }),
+                visibility: Visibility { is_visible: false },
+                ..default()
+            })
+            .insert(bevy::pbr::NotShadowCaster)
+            .insert(Telegraph { bull: entity })
+            .insert(ingame::CleanupMarker);
+    }
+}
+
+fn update_telegraphs(
+    mut commands: Commands,
+    rapier_context: Res<RapierContext>,
+    bulls: Query<(&Transform, &Bull, &Perception)>,
+    bull_collides: Query<Entity, With<ingame::BullCollide>>,
+    mut telegraphs: Query<(&Telegraph, &mut Transform, &mut Visibility), Without<Bull>>,
+    members: Query<(Entity, &GlobalTransform, &groups::GroupMember, Option::<&OutlineVolume>, Option::<&Telegraphed>)>,
+) {
+    let is_obstacle = |entity: Entity| bull_collides.get(entity).is_ok();
+    let filter = QueryFilter::default().exclude_sensors().predicate(&is_obstacle);
+    let mut lanes = vec!();
+
+    for (telegraph, mut transform, mut visibility) in &mut telegraphs {
+        let lane = bulls.get(telegraph.bull)
+                        .ok()
+                        .filter(|(_, bull, _)| bull.state == BullState::Charging || bull.state == BullState::Pawing)
+                        .and_then(|(bull_transform, bull, perception)| {
+                            let player = perception.target()?;
+                            let from = bull_transform.translation;
+                            let direction = Vec3::new(player.x - from.x, 0.0, player.z - from.z).try_normalize()?;
+                            Some((from, direction, predicted_run(bull)))
+                        });
+
+        let (from, direction, max_length) = match lane {
+            Some(lane) => lane,
+            None => {
+                visibility.is_visible = false;
+                continue;
+            },
+        };
+
+        // it runs at the player but whatever is in the way is where it ends up
+        let cast_from = Vec3::new(from.x, from.y.max(MIN_CAST_HEIGHT), from.z);
+        let length = rapier_context.cast_shape(cast_from, Quat::IDENTITY, direction, &Collider::ball(CAST_RADIUS), max_length, filter)
+                                   .map(|(_, toi)| toi.toi)
+                                   .unwrap_or(max_length);
+
+        let start = Vec3::new(from.x, LANE_HEIGHT, from.z);
+        let end = start + direction * length;
+        *transform = Transform::from_translation(start.lerp(end, 0.5))
+                        .with_rotation(Quat::from_rotation_arc(Vec3::Z, direction))
+                        .with_scale(Vec3::new(LANE_WIDTH, 1.0, length));
+        visibility.is_visible = true;
+
+        lanes.push((Vec2::new(start.x, start.z), Vec2::new(end.x, end.z) + Vec2::new(direction.x, direction.z) * CRASH_DEPTH));
+    }
+
+    let threatened = members.iter()
+                            .filter(|(_, transform, _, _, _)| {
+                                let position = transform.translation();
+                                let position = Vec2::new(position.x, position.z);
+                                lanes.iter().any(|(start, end)| distance_to_segment(position, *start, *end) < LANE_WIDTH / 2.0)
+                            })
+                            .map(|(_, _, member, _, _)| member.group_id)
+                            .collect::<HashSet::<_>>();
+
+    for (entity, _, member, outline, telegraphed) in &members {
+        let is_threatened = threatened.contains(&member.group_id);
+        match telegraphed {
+            None if is_threatened => {
+                commands.entity(entity)
+                        .insert(Telegraphed { previous: outline.cloned() })
+                        .insert(OutlineBundle {
+                            outline: threat_outline(),
+                            ..default()
+                        });
+            },
+            Some(telegraphed) if !is_threatened => {
+                let previous = telegraphed.previous.clone().unwrap_or(OutlineVolume {
+                    visible: false,
+                    ..threat_outline()
+                });
+                commands.entity(entity)
+                        .insert(previous)
+                        .remove::<Telegraphed>();
+            },
+            _ => (),
+        }
+    }
+}
+
+// about how far the run goes before it runs out of time
+fn predicted_run(bull: &Bull) -> f32 {
+    (bull.speed * bull::RUN_SPEED * bull.charge_limit * 2.0).min(MAX_LANE_LENGTH)
+}
+
+fn distance_to_segment(point: Vec2, start: Vec2, end: Vec2) -> f32 {
+    let segment = end - start;
+    let t = ((point - start).dot(segment) / segment.length_squared().max(f32::EPSILON)).clamp(0.0, 1.0);
+    point.distance(start + segment * t)
+}
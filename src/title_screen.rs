@@ -1,6 +1,7 @@
 use crate::{
     asset_loading, assets::GameAssets, audio::GameAudio, cleanup, game_controller, menus, 
     ui::text_size, AppState, menus::HOVERED_BUTTON, menus::NORMAL_BUTTON, game_state, assets,
+    game_script, save, arcade,
 };
 use bevy::app::AppExit;
 use bevy::ecs::event::Events;
@@ -11,9 +12,11 @@ pub struct TitlePlugin;
 impl Plugin for TitlePlugin {
     fn build(&self, app: &mut App) {
         app.add_plugin(InputManagerPlugin::<MenuAction>::default())
+            .init_resource::<TitleMenu>()
             .add_system_set(SystemSet::on_enter(AppState::TitleScreen).with_system(setup))
             .add_system_set(
                 SystemSet::on_update(AppState::TitleScreen)
+                    .with_system(build_menu)
                     .with_system(update_menu_buttons.after("handle_input"))
                     .with_system(
                         handle_controllers
@@ -80,6 +83,7 @@ pub fn load(
     assets_handler.add_audio(&mut game_assets.title_screen_bgm, "audio/title_screen_bgm.ogg");
     assets_handler.add_audio(&mut game_assets.blip, "audio/blip.wav");
     assets_handler.add_font(&mut game_assets.font, "fonts/monogram.ttf");
+    assets_handler.add_campaign(&mut game_assets.campaign, "main.campaign.ron");
     assets_handler.add_material(
         &mut game_assets.title_screen_logo,
         "textures/logo.png",
@@ -94,6 +98,7 @@ fn setup(
     mut images: ResMut<Assets<Image>>,
     mut audio: GameAudio,
     mut clear_color: ResMut<ClearColor>,
+    mut title_menu: ResMut<TitleMenu>,
     text_scaler: text_size::TextScaler,
 ) {
     println!("Setting up camera");
@@ -188,14 +193,14 @@ fn setup(
     commands
         .spawn(NodeBundle {
             style: Style {
-                size: Size::new(Val::Percent(30.0), Val::Percent(25.0)),
+                size: Size::new(Val::Percent(30.0), Val::Percent(40.0)),
                 position_type: PositionType::Relative,
                 justify_content: JustifyContent::Center,
                 flex_direction: FlexDirection::Column,
                 margin: UiRect {
                     left: Val::Auto,
                     right: Val::Auto,
-                    top: Val::Percent(40.0),
+                    top: Val::Percent(35.0),
                     ..Default::default()
                 },
                 align_items: AlignItems::FlexEnd,
@@ -205,79 +210,153 @@ fn setup(
             ..Default::default()
         })
         .insert(TitleScreenCleanupMarker)
-        .with_children(|parent| {
-            parent
-                .spawn(ButtonBundle {
-                    style: Style {
-                        position_type: PositionType::Relative,
-                        margin: UiRect::all(Val::Auto),
-                        size: Size::new(Val::Percent(100.0), Val::Percent(40.0)),
-                        justify_content: JustifyContent::Center,
-                        align_items: AlignItems::Center,
-                        ..Default::default()
-                    },
-                    background_color: NORMAL_BUTTON.into(),
-                    ..Default::default()
-                })
-                .with_children(|parent| {
-                    parent.spawn(TextBundle {
-                        text: Text::from_section(
-                            "Start",
-                            TextStyle {
-                                font: game_assets.font.clone(),
-                                font_size: text_scaler.scale(menus::BUTTON_LABEL_FONT_SIZE),
-                                color: Color::rgb(0.0, 0.0, 0.0),
-                            }
-                        ),
-                        ..Default::default()
-                    });
-                })
-                .insert(TitleScreenCleanupMarker);
-
-            parent
-                .spawn(ButtonBundle {
-                    style: Style {
-                        size: Size::new(Val::Percent(100.0), Val::Percent(40.0)),
-                        margin: UiRect::all(Val::Auto),
-                        justify_content: JustifyContent::Center,
-                        align_items: AlignItems::Center,
-                        position_type: PositionType::Relative,
-                        ..Default::default()
-                    },
-                    background_color: NORMAL_BUTTON.into(),
-                    ..Default::default()
-                })
-                .with_children(|parent| {
-                    parent.spawn(TextBundle {
-                        text: Text::from_section(
-                            "Quit",
-                            TextStyle {
-                                font: game_assets.font.clone(),
-                                font_size: text_scaler.scale(menus::BUTTON_LABEL_FONT_SIZE),
-                                color: Color::rgb(0.0, 0.0, 0.0),
-                            }
-                        ),
-                        ..Default::default()
-                    });
-                })
-                .insert(TitleScreenCleanupMarker);
-        });
+        .insert(TitleMenuContainer);
+
+    title_menu.screen = TitleMenuScreen::Main;
+    title_menu.dirty = true;
 
     audio.play_bgm(&game_assets.title_screen_bgm);
 }
 
+#[derive(Component)]
+struct TitleMenuContainer;
+
+#[derive(Component, Copy, Clone)]
+enum TitleButton {
+    Continue,
+    Start,
+    LoadGame,
+    LevelSelect,
+    Arcade,
+    HighScores,
+    Options,
+    Slot(usize),
+    Level(usize),
+    Back,
+    Quit,
+}
+
+#[derive(PartialEq, Default)]
+enum TitleMenuScreen {
+    #[default]
+    Main,
+    Slots,
+    Levels,
+}
+
+#[derive(Resource, Default)]
+struct TitleMenu {
+    screen: TitleMenuScreen,
+    dirty: bool,
+}
+
+fn build_menu(
+    mut commands: Commands,
+    mut title_menu: ResMut<TitleMenu>,
+    containers: Query<Entity, With<TitleMenuContainer>>,
+    game_assets: Res<GameAssets>,
+    save_slots: Res<save::SaveSlots>,
+    progress: Res<save::Progress>,
+    campaigns: Res<Assets<game_script::Campaign>>,
+    text_scaler: text_size::TextScaler,
+) {
+    if !title_menu.dirty {
+        return;
+    }
+    let container = match containers.get_single() {
+        Ok(container) => container,
+        Err(_) => return,
+    };
+    title_menu.dirty = false;
+
+    let buttons = 
+        match title_menu.screen {
+            TitleMenuScreen::Main => {
+                let mut buttons = vec!();
+                if save_slots.latest().is_some() {
+                    buttons.push((TitleButton::Continue, "Continue".to_string()));
+                }
+                buttons.push((TitleButton::Start, "Start".to_string()));
+                buttons.push((TitleButton::LoadGame, "Load Game".to_string()));
+                buttons.push((TitleButton::LevelSelect, "Level Select".to_string()));
+                buttons.push((TitleButton::Arcade, "Arcade".to_string()));
+                buttons.push((TitleButton::HighScores, "High Scores".to_string()));
+                buttons.push((TitleButton::Options, "Options".to_string()));
+                buttons.push((TitleButton::Quit, "Quit".to_string()));
+                buttons
+            },
+            TitleMenuScreen::Slots => {
+                let mut buttons = 
+                    save_slots.slots.iter()
+                                    .enumerate()
+                                    .map(|(i, slot)| {
+                                        let label = match slot {
+                                            Some(save) => format!("{}: {} {:.0}%", i + 1, save.chapter.replace("_", " "), save.score * 100.0),
+                                            None => format!("{}: Empty", i + 1),
+                                        };
+                                        (TitleButton::Slot(i), label)
+                                    })
+                                    .collect::<Vec::<_>>();
+                buttons.push((TitleButton::Back, "Back".to_string()));
+                buttons
+            },
+            TitleMenuScreen::Levels => {
+                let levels = campaigns.get(&game_assets.campaign)
+                                      .map(|campaign| campaign.levels())
+                                      .unwrap_or_default();
+                let mut buttons = 
+                    levels.iter()
+                          .enumerate()
+                          .map(|(i, level)| {
+                              let name = level.id.replace("_", " ");
+                              let label = 
+                                  if i > 0 && !progress.is_unlocked(&level.id) {
+                                      format!("{} - locked", name)
+                                  } else if let Some(best) = progress.best_scores.get(&level.id) {
+                                      format!("{} - {:.0}%", name, best * 100.0)
+                                  } else {
+                                      name
+                                  };
+                              (TitleButton::Level(i), label)
+                          })
+                          .collect::<Vec::<_>>();
+                buttons.push((TitleButton::Back, "Back".to_string()));
+                buttons
+            }
+        };
+
+    let button_height = 90.0 / buttons.len() as f32;
+    commands.entity(container).despawn_descendants();
+    commands.entity(container).with_children(|parent| {
+        for (title_button, label) in buttons {
+            menus::spawn_button(
+                parent,
+                game_assets.font.clone(),
+                text_scaler.scale(menus::BUTTON_LABEL_FONT_SIZE),
+                button_height,
+                &label,
+                title_button,
+            );
+        }
+    });
+}
+
 fn update_menu_buttons(
     mut selected_button: Local<usize>,
     mut exit: ResMut<Events<AppExit>>,
-    buttons: Query<Entity, With<Button>>,
+    mut state: ResMut<State<AppState>>,
+    buttons: Query<&TitleButton, With<Button>>,
     mut button_colors: Query<&mut BackgroundColor, With<Button>>,
-    interaction_query: Query<(Entity, &Interaction), (Changed<Interaction>, With<Button>)>,
     action_state: Query<&ActionState<MenuAction>>,
-    //mut assets_handler: asset_loading::AssetsHandler,
     mut audio: GameAudio,
     mut assets_handler: asset_loading::AssetsHandler,
     mut game_assets: ResMut<assets::GameAssets>,
     mut game_state: ResMut<game_state::GameState>,
+    mut game_script_state: ResMut<game_script::GameScriptState>,
+    // grouped to stay under the system param limit
+    (mut save_slots, progress, mut arcade): (ResMut<save::SaveSlots>, Res<save::Progress>, ResMut<arcade::Arcade>),
+    mut title_menu: ResMut<TitleMenu>,
+    campaigns: Res<Assets<game_script::Campaign>>,
     time: Res<Time>,
 ) {
     if game_state.title_screen_cooldown > 0.0 {
@@ -287,7 +366,10 @@ fn update_menu_buttons(
 
     let action_state = action_state.single();
     let number_of_buttons = buttons.iter().count();
-    let mut pressed_button = action_state.pressed(MenuAction::Select);
+    if number_of_buttons == 0 {
+        return;
+    }
+    let pressed_button = action_state.pressed(MenuAction::Select);
 
     if action_state.just_pressed(MenuAction::Up) {
         audio.play_sfx(&game_assets.blip);
@@ -304,7 +386,7 @@ fn update_menu_buttons(
             new_selected_button
         };
     }
-
+    *selected_button = (*selected_button).min(number_of_buttons - 1);
 
     for (i, mut color) in button_colors.iter_mut().enumerate() {
         if i == *selected_button {
@@ -315,12 +397,78 @@ fn update_menu_buttons(
     }
 
     if pressed_button {
-        if *selected_button == 0 {
-            audio.play_sfx(&game_assets.blip);
-            assets_handler.load(AppState::LoadWorld, &mut game_assets, &game_state);
-        }
-        if *selected_button == 1 {
-            exit.send(AppExit);
+        let title_button = buttons.iter().nth(*selected_button).copied();
+        audio.play_sfx(&game_assets.blip);
+        match title_button {
+            Some(TitleButton::Continue) => {
+                if let Some(slot) = save_slots.latest() {
+                    save_slots.load_game(slot, &mut game_script_state, &mut game_state);
+                    assets_handler.load(AppState::LoadWorld, &mut game_assets, &game_state);
+                }
+            },
+            Some(TitleButton::Start) => {
+                let slot = save_slots.free_slot();
+                save_slots.new_game(slot, &mut game_script_state, &mut game_state);
+                assets_handler.load(AppState::LoadWorld, &mut game_assets, &game_state);
+            },
+            Some(TitleButton::Slot(slot)) => {
+                save_slots.load_game(slot, &mut game_script_state, &mut game_state);
+                assets_handler.load(AppState::LoadWorld, &mut game_assets, &game_state);
+            },
+            Some(TitleButton::Level(i)) => {
+                let entry = campaigns.get(&game_assets.campaign).and_then(|campaign| {
+                    campaign.levels()
+                            .get(i)
+                            .filter(|level| i == 0 || progress.is_unlocked(&level.id))
+                            .map(|level| campaign.level_entry(&level.id).to_string())
+                });
+                if let Some(entry) = entry {
+                    save_slots.active = None;
+                    game_script_state.current = entry;
+                    game_state.score = 1.0;
+                    game_state.score_check_count = 0;
+                    assets_handler.load(AppState::LoadWorld, &mut game_assets, &game_state);
+                }
+            },
+            Some(TitleButton::Arcade) => {
+                let started = campaigns.get(&game_assets.campaign)
+                                       .map(|campaign| arcade.start(campaign, &mut game_script_state))
+                                       .unwrap_or(false);
+                if started {
+                    save_slots.active = None;
+                    game_state.score = 1.0;
+                    game_state.score_check_count = 0;
+                    assets_handler.load(AppState::LoadWorld, &mut game_assets, &game_state);
+                }
+            },
+            Some(TitleButton::LoadGame) => {
+                title_menu.screen = TitleMenuScreen::Slots;
+                title_menu.dirty = true;
+                *selected_button = 0;
+                game_state.title_screen_cooldown = 0.3;
+            },
+            Some(TitleButton::LevelSelect) => {
+                title_menu.screen = TitleMenuScreen::Levels;
+                title_menu.dirty = true;
+                *selected_button = 0;
+                game_state.title_screen_cooldown = 0.3;
+            },
+            Some(TitleButton::HighScores) => {
+                state.push(AppState::Leaderboard).unwrap();
+            },
+            Some(TitleButton::Options) => {
+                state.push(AppState::Options).unwrap();
+            },
+            Some(TitleButton::Back) => {
+                title_menu.screen = TitleMenuScreen::Main;
+                title_menu.dirty = true;
+                *selected_button = 0;
+                game_state.title_screen_cooldown = 0.3;
+            },
+            Some(TitleButton::Quit) => {
+                exit.send(AppExit);
+            },
+            None => (),
         }
     }
 }